@@ -1,42 +1,111 @@
-use super::SafeApplicationWindow;
+use super::UIBuilder;
+#[cfg(feature = "x11")]
+use super::{x11_dock::X11Dock, SafeX11Dock};
+use gtk::ApplicationWindow;
+#[cfg(feature = "x11")]
+use gtk::{gdk::Surface, glib::SignalHandlerId, prelude::*};
 use gtk4_layer_shell::*;
 use rune::Module;
+#[cfg(feature = "x11")]
+use std::{cell::RefCell, rc::Rc};
+
+/// Error returned when a window is configured before `init_layer_shell` has been called for it.
+const NOT_INITIALIZED_ERROR: &str = "init_layer_shell hasn't been called for the current window!";
+
+/// Error returned when no backend is available for turning windows into panels.
+#[cfg(feature = "x11")]
+const UNSUPPORTED_ERROR: &str = "Neither the layer-shell nor X11 are available!";
+
+/// Error returned when no backend is available for turning windows into panels.
+#[cfg(not(feature = "x11"))]
+const UNSUPPORTED_ERROR: &str =
+    "The layer-shell isn't available, and X11 support requires the \"x11\" feature!";
 
 /// Rune module for managing the layer shell.
 pub struct LayerShellCrate;
 
+/// Backend used for turning windows into panels.
+#[derive(Clone, Copy)]
+enum Backend {
+    /// The wlr-layer-shell protocol.
+    LayerShell,
+
+    /// Dock-type windows with strut properties, on X11.
+    #[cfg(feature = "x11")]
+    X11,
+
+    /// Neither of the above is available.
+    None,
+}
+
+/// Window being configured by a layer-shell function, along with its dock settings on X11.
+enum BackendWindow<'a> {
+    /// A window which has been turned into a layer-shell.
+    LayerShell(&'a ApplicationWindow),
+
+    /// A window emulating the layer-shell on X11.
+    #[cfg(feature = "x11")]
+    X11(&'a mut X11Dock, &'a ApplicationWindow),
+}
+
 impl LayerShellCrate {
     /// Builds the Layer Shell Module.
-    pub fn build(
-        application_window: &'static SafeApplicationWindow,
-        script_relative_path: &'static str,
-    ) -> Module {
+    /// All functions operate on the window targeted by `GTK::set_current_window`.
+    /// When the layer-shell isn't supported and the `x11` feature is enabled, X11 dock windows are
+    /// used instead.
+    pub fn build(ui_builder: &'static UIBuilder, script_relative_path: &'static str) -> Module {
         let mut built_crate = Module::with_crate("LayerShell")
             .expect("[ERROR] Failed building the LayerShell crate!");
+        built_crate
+            .function("get_backend", || match Self::get_backend() {
+                Backend::LayerShell => "LayerShell".to_owned(),
+                #[cfg(feature = "x11")]
+                Backend::X11 => "X11".to_owned(),
+                Backend::None => "None".to_owned(),
+            })
+            .build()
+            .unwrap();
+
         built_crate
             .function(
                 "init_layer_shell",
                 move |enable_exclusive_zone, layer: String| {
-                    application_window.0.init_layer_shell();
-                    application_window.0.set_namespace(script_relative_path);
-                    if enable_exclusive_zone {
-                        application_window.0.auto_exclusive_zone_enable();
-                    }
+                    let layer = Self::get_layer_from_str(&layer)?;
+                    let window = ui_builder.get_current_window();
+                    match Self::get_backend() {
+                        Backend::LayerShell => {
+                            // The window stays a layer-shell when the script is reloaded.
+                            if !window.is_layer_window() {
+                                window.init_layer_shell();
+                            }
 
-                    match layer.as_str() {
-                        "Top" => application_window.0.set_layer(Layer::Top),
-                        "Bottom" => application_window.0.set_layer(Layer::Bottom),
-                        "Overlay" => application_window.0.set_layer(Layer::Overlay),
-                        "Background" => application_window.0.set_layer(Layer::Background),
-                        _ => panic!("[ERROR] Invalid layer value!"),
+                            window.set_namespace(script_relative_path);
+                            if enable_exclusive_zone {
+                                window.auto_exclusive_zone_enable();
+                            }
+
+                            window.set_layer(layer);
+                        }
+                        #[cfg(feature = "x11")]
+                        Backend::X11 => {
+                            Self::init_x11_dock(ui_builder, &window);
+                            Self::with_x11_dock(ui_builder, |dock, window| {
+                                dock.auto_exclusive_zone |= enable_exclusive_zone;
+                                dock.layer = layer;
+                                dock.apply(window);
+                            })?;
+                        }
+                        Backend::None => return Err(UNSUPPORTED_ERROR.to_owned()),
                     }
+
+                    Ok::<_, String>(())
                 },
             )
             .build()
             .unwrap();
 
         built_crate
-            .function("set_anchors", |left, right, top, bottom| {
+            .function("set_anchors", move |left, right, top, bottom| {
                 let anchors = [
                     (Edge::Left, left),
                     (Edge::Right, right),
@@ -44,13 +113,324 @@ impl LayerShellCrate {
                     (Edge::Bottom, bottom),
                 ];
 
-                for (anchor, state) in anchors {
-                    application_window.0.set_anchor(anchor, state);
-                }
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => {
+                        for (anchor, state) in anchors {
+                            window.set_anchor(anchor, state);
+                        }
+                    }
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, window) => {
+                        dock.anchors = [left, right, top, bottom];
+                        dock.apply(window);
+                    }
+                })
+            })
+            .build()
+            .unwrap();
+
+        built_crate
+            .function("get_anchors", move || {
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => (
+                        window.is_anchor(Edge::Left),
+                        window.is_anchor(Edge::Right),
+                        window.is_anchor(Edge::Top),
+                        window.is_anchor(Edge::Bottom),
+                    ),
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, _) => {
+                        let [left, right, top, bottom] = dock.anchors;
+                        (left, right, top, bottom)
+                    }
+                })
+            })
+            .build()
+            .unwrap();
+
+        built_crate
+            .function("set_layer", move |layer: String| {
+                let layer = Self::get_layer_from_str(&layer)?;
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => window.set_layer(layer),
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, _) => dock.layer = layer,
+                })
+            })
+            .build()
+            .unwrap();
+
+        built_crate
+            .function("get_layer", move || {
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => Self::get_str_from_layer(window.layer()),
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, _) => Self::get_str_from_layer(dock.layer),
+                })
+            })
+            .build()
+            .unwrap();
+
+        built_crate
+            .function("set_margin", move |edge: String, margin: i32| {
+                let edge = Self::get_edge_from_str(&edge)?;
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => window.set_margin(edge, margin),
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, window) => {
+                        dock.margins[Self::get_edge_index(edge)] = margin;
+                        dock.apply(window);
+                    }
+                })
+            })
+            .build()
+            .unwrap();
+
+        built_crate
+            .function("get_margin", move |edge: String| {
+                let edge = Self::get_edge_from_str(&edge)?;
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => window.margin(edge),
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, _) => dock.margins[Self::get_edge_index(edge)],
+                })
+            })
+            .build()
+            .unwrap();
+
+        built_crate
+            .function("set_exclusive_zone", move |exclusive_zone: i32| {
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => window.set_exclusive_zone(exclusive_zone),
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, window) => {
+                        dock.auto_exclusive_zone = false;
+                        dock.exclusive_zone = exclusive_zone;
+                        dock.apply(window);
+                    }
+                })
             })
             .build()
             .unwrap();
 
         built_crate
+            .function("get_exclusive_zone", move || {
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => window.exclusive_zone(),
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, _) => dock.exclusive_zone,
+                })
+            })
+            .build()
+            .unwrap();
+
+        built_crate
+            .function("set_keyboard_mode", move |keyboard_mode: String| {
+                let keyboard_mode = Self::get_keyboard_mode_from_str(&keyboard_mode)?;
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => window.set_keyboard_mode(keyboard_mode),
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, _) => dock.keyboard_mode = keyboard_mode,
+                })
+            })
+            .build()
+            .unwrap();
+
+        built_crate
+            .function("get_keyboard_mode", move || {
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => {
+                        Self::get_str_from_keyboard_mode(window.keyboard_mode())
+                    }
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, _) => {
+                        Self::get_str_from_keyboard_mode(dock.keyboard_mode)
+                    }
+                })
+            })
+            .build()
+            .unwrap();
+
+        built_crate
+            .function("set_monitor", move |connector: String| {
+                let monitor = UIBuilder::get_monitor(&connector)
+                    .ok_or_else(|| format!("No monitor is connected as \"{connector}\"!"))?;
+                Self::with_backend(ui_builder, |backend_window| match backend_window {
+                    BackendWindow::LayerShell(window) => window.set_monitor(&monitor),
+                    #[cfg(feature = "x11")]
+                    BackendWindow::X11(dock, window) => {
+                        dock.monitor = Some(monitor.to_owned());
+                        dock.apply(window);
+                    }
+                })
+            })
+            .build()
+            .unwrap();
+
+        built_crate
+    }
+
+    /// Gets the backend used for turning windows into panels.
+    fn get_backend() -> Backend {
+        if is_supported() {
+            return Backend::LayerShell;
+        }
+
+        #[cfg(feature = "x11")]
+        if X11Dock::is_supported() {
+            return Backend::X11;
+        }
+
+        Backend::None
+    }
+
+    /// Calls `function` with the current window, along with its dock settings on X11.
+    /// Returns an error if `init_layer_shell` hasn't been called for the current window.
+    fn with_backend<R>(
+        ui_builder: &UIBuilder,
+        function: impl FnOnce(BackendWindow) -> R,
+    ) -> Result<R, String> {
+        match Self::get_backend() {
+            Backend::LayerShell => {
+                let window = ui_builder.get_current_window();
+                if !window.is_layer_window() {
+                    return Err(NOT_INITIALIZED_ERROR.to_owned());
+                }
+
+                Ok(function(BackendWindow::LayerShell(&window)))
+            }
+            #[cfg(feature = "x11")]
+            Backend::X11 => Self::with_x11_dock(ui_builder, |dock, window| {
+                function(BackendWindow::X11(dock, window))
+            }),
+            Backend::None => Err(UNSUPPORTED_ERROR.to_owned()),
+        }
+    }
+
+    /// Calls `function` with the dock settings of the current window and the window itself.
+    #[cfg(feature = "x11")]
+    fn with_x11_dock<R>(
+        ui_builder: &UIBuilder,
+        function: impl FnOnce(&mut X11Dock, &ApplicationWindow) -> R,
+    ) -> Result<R, String> {
+        let window = ui_builder.get_current_window();
+        let current_window = ui_builder.current_window.lock().to_owned();
+        let mut x11_docks = ui_builder.x11_docks.lock();
+        let dock = x11_docks
+            .get_mut(&current_window)
+            .ok_or(NOT_INITIALIZED_ERROR)?;
+
+        Ok(function(&mut dock.0, &window))
+    }
+
+    /// Creates the dock settings of `window` if they don't exist yet, keeping them applied
+    /// whenever the window is realized or resized.
+    #[cfg(feature = "x11")]
+    fn init_x11_dock(ui_builder: &'static UIBuilder, window: &ApplicationWindow) {
+        let identifier = ui_builder.current_window.lock().to_owned();
+        let mut x11_docks = ui_builder.x11_docks.lock();
+        if x11_docks.contains_key(&identifier) {
+            return;
+        }
+
+        x11_docks.insert(identifier.to_owned(), SafeX11Dock(X11Dock::default()));
+
+        // Each realization creates a new surface, so the layout handler of the previous one is
+        // disconnected first.
+        let layout_handler: Rc<RefCell<Option<(Surface, SignalHandlerId)>>> = Rc::default();
+        window.connect_realize(move |window| {
+            if let Some(dock) = ui_builder.x11_docks.lock().get(&identifier) {
+                dock.0.apply(window);
+            }
+
+            if let Some((surface, handler_id)) = layout_handler.take() {
+                surface.disconnect(handler_id);
+            }
+
+            let Some(surface) = window.surface() else {
+                return;
+            };
+
+            let identifier = identifier.to_owned();
+            let window = window.to_owned();
+            let handler_id = surface.connect_layout(move |_, _, _| {
+                if let Some(dock) = ui_builder.x11_docks.lock().get(&identifier) {
+                    dock.0.apply(&window);
+                }
+            });
+            layout_handler.replace(Some((surface, handler_id)));
+        });
+    }
+
+    /// Gets the `Layer` value from the `layer` string-slice.
+    fn get_layer_from_str(layer: &str) -> Result<Layer, String> {
+        match layer {
+            "Top" => Ok(Layer::Top),
+            "Bottom" => Ok(Layer::Bottom),
+            "Overlay" => Ok(Layer::Overlay),
+            "Background" => Ok(Layer::Background),
+            _ => Err(format!(
+                "Invalid layer \"{layer}\", use Top, Bottom, Overlay or Background!"
+            )),
+        }
+    }
+
+    /// Gets the string representation of `layer`.
+    fn get_str_from_layer(layer: Layer) -> String {
+        match layer {
+            Layer::Top => "Top",
+            Layer::Bottom => "Bottom",
+            Layer::Overlay => "Overlay",
+            Layer::Background => "Background",
+            _ => "Unknown",
+        }
+        .to_owned()
+    }
+
+    /// Gets the `Edge` value from the `edge` string-slice.
+    fn get_edge_from_str(edge: &str) -> Result<Edge, String> {
+        match edge {
+            "Left" => Ok(Edge::Left),
+            "Right" => Ok(Edge::Right),
+            "Top" => Ok(Edge::Top),
+            "Bottom" => Ok(Edge::Bottom),
+            _ => Err(format!(
+                "Invalid edge \"{edge}\", use Left, Right, Top or Bottom!"
+            )),
+        }
+    }
+
+    /// Gets the index of `edge` in the settings of an `X11Dock`.
+    #[cfg(feature = "x11")]
+    fn get_edge_index(edge: Edge) -> usize {
+        match edge {
+            Edge::Left => 0,
+            Edge::Right => 1,
+            Edge::Top => 2,
+            _ => 3,
+        }
+    }
+
+    /// Gets the `KeyboardMode` value from the `keyboard_mode` string-slice.
+    fn get_keyboard_mode_from_str(keyboard_mode: &str) -> Result<KeyboardMode, String> {
+        match keyboard_mode {
+            "None" => Ok(KeyboardMode::None),
+            "Exclusive" => Ok(KeyboardMode::Exclusive),
+            "OnDemand" => Ok(KeyboardMode::OnDemand),
+            _ => Err(format!(
+                "Invalid keyboard mode \"{keyboard_mode}\", use None, Exclusive or OnDemand!"
+            )),
+        }
+    }
+
+    /// Gets the string representation of `keyboard_mode`.
+    fn get_str_from_keyboard_mode(keyboard_mode: KeyboardMode) -> String {
+        match keyboard_mode {
+            KeyboardMode::None => "None",
+            KeyboardMode::Exclusive => "Exclusive",
+            KeyboardMode::OnDemand => "OnDemand",
+            _ => "Unknown",
+        }
+        .to_owned()
     }
 }
@@ -1,17 +1,34 @@
 mod fs_crate;
 mod layer_shell_crate;
+mod script_scope;
 pub mod stdext_crate;
+mod tree_builder;
+mod widget_handle;
+#[cfg(feature = "x11")]
+mod x11_dock;
 
 use crate::{config::Config, script::ScriptEngine};
 use fs_crate::FileSystemCrate;
 use gtk::{gdk::Display, prelude::*, Application, ApplicationWindow, CssProvider, Widget};
+use gtk4_layer_shell::LayerShell;
 use layer_shell_crate::LayerShellCrate;
 use parking_lot::{Mutex, RwLock};
-use rune::{runtime::Function, Module, Value};
+use rune::{
+    runtime::{Function, ToValue},
+    Module, Value,
+};
+use script_scope::ScriptScope;
 use std::{
-    collections::HashMap,
+    cell::Cell,
+    collections::{hash_map::Entry, HashMap},
+    rc::Rc,
     sync::{Arc, OnceLock},
+    time::Duration,
 };
+use tree_builder::TreeBuilder;
+use widget_handle::WidgetHandle;
+#[cfg(feature = "x11")]
+use x11_dock::X11Dock;
 
 /// Wrapper around `ApplicationWindow` which implements `Sync` in an unsafe way.
 struct SafeApplicationWindow(pub ApplicationWindow);
@@ -19,12 +36,33 @@ struct SafeApplicationWindow(pub ApplicationWindow);
 /// Wrapper around `Widget` which implements `Send` in an unsafe way.
 struct SafeGTKWidget(pub Widget);
 
+/// Wrapper around `CssProvider` which implements `Send` in an unsafe way.
+struct SafeCssProvider(pub CssProvider);
+
+/// Wrapper around `X11Dock` which implements `Send` in an unsafe way.
+#[cfg(feature = "x11")]
+struct SafeX11Dock(pub X11Dock);
+
+/// Wrapper around `FileMonitor` which implements `Send` in an unsafe way.
+struct SafeFileMonitor(pub gtk::gio::FileMonitor);
+
 // Force-implement traits so that the structures can be accessed through Rune.
 // Safety: This should be safe, as Rune runs on the main thread and Crisp does
 // ------- not modify widgets nor access them through other threads.
+unsafe impl Send for SafeApplicationWindow {}
 unsafe impl Sync for SafeApplicationWindow {}
 unsafe impl Send for SafeGTKWidget {}
 unsafe impl Sync for SafeGTKWidget {}
+unsafe impl Send for SafeCssProvider {}
+#[cfg(feature = "x11")]
+unsafe impl Send for SafeX11Dock {}
+unsafe impl Send for SafeFileMonitor {}
+
+/// Name given to the motion controllers added by `UIBuilder::connect_enter_exit_events`.
+const ENTER_EXIT_CONTROLLER_NAME: &str = "crisp-enter-exit";
+
+/// Time to wait for more changes to a watched file before reacting to them.
+const FILE_CHANGE_DELAY: Duration = Duration::from_millis(100);
 
 type UserWidgets = Arc<RwLock<HashMap<String, SafeGTKWidget>>>;
 type CurrentUserWidget = Arc<Mutex<Option<String>>>;
@@ -38,6 +76,32 @@ pub struct UIBuilder {
     /// The currently focused user widget identifier.
     current_user_widget: CurrentUserWidget,
 
+    /// All created windows, by the identifier of their root box widget.
+    windows: RwLock<HashMap<String, SafeApplicationWindow>>,
+
+    /// Identifier of the window targeted by the window and layer-shell functions.
+    current_window: Mutex<String>,
+
+    /// Connector names of the monitors which have a window created for them, or `None` if windows
+    /// aren't being created per monitor.
+    monitor_windows: Mutex<Option<Vec<String>>>,
+
+    /// Layer-shell settings of the windows emulating it on X11, by their identifier.
+    #[cfg(feature = "x11")]
+    x11_docks: Mutex<HashMap<String, SafeX11Dock>>,
+
+    /// Whether windows let input pass through to whatever is below them, by their identifier.
+    click_through_windows: Mutex<HashMap<String, bool>>,
+
+    /// Index at which the next widget should be inserted, instead of being appended.
+    insertion_index: Mutex<Option<usize>>,
+
+    /// CSS providers loaded by the script, by their identifier.
+    css_providers: Mutex<HashMap<String, SafeCssProvider>>,
+
+    /// Monitors of the watched files, by their path.
+    file_monitors: Mutex<HashMap<String, SafeFileMonitor>>,
+
     script_engine: OnceLock<Arc<ScriptEngine>>,
 }
 
@@ -54,9 +118,12 @@ impl UIBuilder {
 
         app.connect_startup(|_| self.load_css());
         app.connect_activate(move |app| {
-            let application_window: &'static SafeApplicationWindow = Box::leak(Box::new(
-                SafeApplicationWindow(ApplicationWindow::builder().application(app).build()),
-            ));
+            let application_window = ApplicationWindow::builder().application(app).build();
+            self.windows.write().insert(
+                "root".to_owned(),
+                SafeApplicationWindow(application_window.to_owned()),
+            );
+            *self.current_window.lock() = "root".to_owned();
 
             let script_relative_path = script_relative_path.to_owned().leak();
 
@@ -64,526 +131,2981 @@ impl UIBuilder {
                 Module::with_crate("GTK").expect("[ERROR] Failed building GTK crate!");
             gtk_module
                 .function("set_window_title", move |title: Option<String>| {
-                    application_window.0.set_title(title.as_deref());
+                    self.get_current_window().set_title(title.as_deref());
                 })
                 .build()
                 .unwrap();
             gtk_module
                 .function("set_window_height_request", |height| {
-                    application_window.0.set_height_request(height)
+                    self.get_current_window().set_height_request(height)
                 })
                 .build()
                 .unwrap();
             gtk_module
                 .function("set_window_width_request", |width| {
-                    application_window.0.set_width_request(width)
+                    self.get_current_window().set_width_request(width)
                 })
                 .build()
                 .unwrap();
             gtk_module
                 .function("set_window_default_height", |height| {
-                    application_window.0.set_default_height(height)
+                    self.get_current_window().set_default_height(height)
                 })
                 .build()
                 .unwrap();
             gtk_module
                 .function("set_window_default_width", |width| {
-                    application_window.0.set_default_width(width)
+                    self.get_current_window().set_default_width(width)
                 })
                 .build()
                 .unwrap();
             gtk_module
                 .function("set_window_resizable", |resizable| {
-                    application_window.0.set_resizable(resizable)
+                    self.get_current_window().set_resizable(resizable)
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_focused_widget", move |identifier: String| {
-                    self.set_focused_widget(identifier);
+                .function("set_click_through", |click_through: bool| {
+                    self.set_click_through(click_through)
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("add_vertical_box", |identifier: String, spacing| {
-                    self.add_widget(
-                        identifier,
-                        gtk::Box::new(gtk::Orientation::Vertical, spacing),
-                    )
+                .function("create_window", |identifier: String| {
+                    self.create_window(identifier)
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("add_horizontal_box", |identifier: String, spacing| {
-                    self.add_widget(
-                        identifier,
-                        gtk::Box::new(gtk::Orientation::Horizontal, spacing),
-                    )
+                .function("set_current_window", |identifier: String| {
+                    if !self.windows.read().contains_key(&identifier) {
+                        eprintln!("[ERROR] No window has been created as \"{identifier}\"!");
+                        return false;
+                    }
+
+                    *self.current_window.lock() = identifier;
+                    true
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("add_label", |identifier: String, text: String| {
-                    self.add_widget(identifier, gtk::Label::new(Some(&text)))
+                .function("get_current_window", || {
+                    self.current_window.lock().to_owned()
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_visible", |visible| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_visible(visible)
+                .function("show_window", |identifier: String| {
+                    self.get_window(&identifier)
+                        .map(|window| window.present())
+                        .is_some()
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_opacity", |opacity| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_opacity(opacity)
+                .function("hide_window", |identifier: String| {
+                    self.get_window(&identifier)
+                        .map(|window| window.set_visible(false))
+                        .is_some()
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_hexpand", |expand| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_hexpand(expand)
+                .function("destroy_window", |identifier: String| {
+                    self.destroy_window(&identifier)
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_vexpand", |expand| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_vexpand(expand)
+                .function("get_monitors", || {
+                    Self::get_monitors()
+                        .iter()
+                        .filter_map(|monitor| monitor.connector().map(|connector| connector.into()))
+                        .collect::<Vec<String>>()
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_gtk_widget_name", |name: String| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_widget_name(&name)
+                .function("enable_per_monitor_windows", || {
+                    self.monitor_windows.lock().get_or_insert_with(Vec::new);
+                    gtk::glib::idle_add_local_once(|| self.sync_monitor_windows());
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_tooltip_text", |text: Option<String>| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_tooltip_text(text.as_deref())
+                .function("set_focused_widget", move |identifier: String| {
+                    self.set_focused_widget(identifier);
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_tooltip_markup", |text: Option<String>| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_tooltip_markup(text.as_deref())
+                .function("add_vertical_box", |identifier: String, spacing| {
+                    self.add_widget(
+                        identifier,
+                        gtk::Box::new(gtk::Orientation::Vertical, spacing),
+                    )
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("get_width", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .width()
+                .function("add_horizontal_box", |identifier: String, spacing| {
+                    self.add_widget(
+                        identifier,
+                        gtk::Box::new(gtk::Orientation::Horizontal, spacing),
+                    )
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("get_width_request", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .width_request()
-                })
+                .function(
+                    "add_grid",
+                    |identifier: String, row_spacing: u32, column_spacing: u32| {
+                        let grid = gtk::Grid::new();
+                        grid.set_row_spacing(row_spacing);
+                        grid.set_column_spacing(column_spacing);
+                        self.add_widget(identifier, grid)
+                    },
+                )
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_width_request", |width| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_width_request(width)
-                })
+                .function(
+                    "set_grid_position",
+                    |column: i32, row: i32, column_span: i32, row_span: i32| {
+                        let user_widgets = self.user_widgets.read();
+                        let widget = &self
+                            .get_current_gtk_widget(&user_widgets)
+                            .expect("[ERROR] Couldn't get the current widget!")
+                            .0;
+                        let Some(grid) = widget
+                            .parent()
+                            .and_then(|parent| parent.downcast::<gtk::Grid>().ok())
+                        else {
+                            eprintln!("[ERROR] The parent of the current widget isn't a grid!");
+                            return;
+                        };
+
+                        let layout_child = grid
+                            .layout_manager()
+                            .expect("[ERROR] Grid has no layout manager!")
+                            .layout_child(widget)
+                            .downcast::<gtk::GridLayoutChild>()
+                            .expect("[ERROR] Grid layout child isn't a GridLayoutChild!");
+                        layout_child.set_column(column);
+                        layout_child.set_row(row);
+                        layout_child.set_column_span(column_span);
+                        layout_child.set_row_span(row_span);
+                    },
+                )
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("get_height", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .height()
+                .function("add_center_box", |identifier: String| {
+                    self.add_widget(identifier, gtk::CenterBox::new())
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("get_height_request", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .height_request()
+                .function("set_center_box_slot", |slot: String| {
+                    Self::set_center_box_slot(
+                        &self
+                            .get_current_gtk_widget(&self.user_widgets.read())
+                            .expect("[ERROR] Couldn't get the current widget!")
+                            .0,
+                        &slot,
+                    )
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("get_opacity", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .opacity()
-                })
+                .function(
+                    "add_flow_box",
+                    |identifier: String, max_children_per_line: u32| {
+                        let flow_box = gtk::FlowBox::new();
+                        flow_box.set_selection_mode(gtk::SelectionMode::None);
+                        flow_box.set_max_children_per_line(max_children_per_line);
+                        self.add_widget(identifier, flow_box)
+                    },
+                )
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("can_focus", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .can_focus()
+                .function("add_stack", |identifier: String| {
+                    self.add_widget(identifier, gtk::Stack::new())
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("is_focus", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .is_focus()
+                .function("set_visible_stack_page", |identifier: String| {
+                    self.try_get_current_gtk_widget_as::<gtk::Stack>(&self.user_widgets.read())
+                        .unwrap()
+                        .set_visible_child_name(&identifier)
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("has_focus", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .has_focus()
+                .function("get_visible_stack_page", || {
+                    self.try_get_current_gtk_widget_as::<gtk::Stack>(&self.user_widgets.read())
+                        .unwrap()
+                        .visible_child_name()
+                        .map(|name| name.to_string())
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("can_target", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
+                .function("set_stack_page_title", |title: String| {
+                    let user_widgets = self.user_widgets.read();
+                    let widget = &self
+                        .get_current_gtk_widget(&user_widgets)
                         .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .can_target()
+                        .0;
+                    let Some(stack) = widget
+                        .parent()
+                        .and_then(|parent| parent.downcast::<gtk::Stack>().ok())
+                    else {
+                        eprintln!("[ERROR] The parent of the current widget isn't a stack!");
+                        return;
+                    };
+
+                    stack.page(widget).set_title(&title);
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("is_visible", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .is_visible()
+                .function(
+                    "add_scrolled_window",
+                    |identifier: String, hpolicy: String, vpolicy: String| {
+                        let scrolled_window = gtk::ScrolledWindow::new();
+                        scrolled_window.set_policy(
+                            Self::get_policy_from_str(&hpolicy)?,
+                            Self::get_policy_from_str(&vpolicy)?,
+                        );
+                        Ok::<_, String>(self.add_widget(identifier, scrolled_window))
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_scroll_position", || {
+                    let user_widgets = self.user_widgets.read();
+                    let scrolled_window = self
+                        .try_get_current_gtk_widget_as::<gtk::ScrolledWindow>(&user_widgets)
+                        .unwrap();
+                    (
+                        scrolled_window.hadjustment().value(),
+                        scrolled_window.vadjustment().value(),
+                    )
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("is_focusable", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .is_focusable()
+                .function("set_scroll_position", |x: f64, y: f64| {
+                    let user_widgets = self.user_widgets.read();
+                    let scrolled_window = self
+                        .try_get_current_gtk_widget_as::<gtk::ScrolledWindow>(&user_widgets)
+                        .unwrap();
+                    scrolled_window.hadjustment().set_value(x);
+                    scrolled_window.vadjustment().set_value(y);
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("scale_factor", || {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .scale_factor()
+                .function("scroll_to_child", |identifier: String| {
+                    let user_widgets = self.user_widgets.read();
+                    let Some(child) = user_widgets.get(&identifier) else {
+                        eprintln!("[ERROR] No widget has been defined as \"{identifier}\"!");
+                        return;
+                    };
+
+                    let Some(viewport) = self
+                        .try_get_current_gtk_widget_as::<gtk::ScrolledWindow>(&user_widgets)
+                        .unwrap()
+                        .child()
+                        .and_then(|child| child.downcast::<gtk::Viewport>().ok())
+                    else {
+                        eprintln!(
+                            "[ERROR] The child of the current scrolled window can't be scrolled to!"
+                        );
+                        return;
+                    };
+
+                    viewport.scroll_to(&child.0, None);
                 })
                 .build()
                 .unwrap();
 
             gtk_module
                 .function(
-                    "add_button",
-                    move |identifier: String,
-                          label: String,
-                          callback: Function,
-                          callback_arg: Option<Value>| {
-                        let identifier_clone = identifier.to_owned();
-                        let button = gtk::Button::with_label(&label);
-
-                        button.connect_clicked(move |_| {
-                            Self::execute_callback(&identifier_clone, &callback, &callback_arg);
+                    "set_edge_reached_callback",
+                    |callback: Function, callback_arg: Option<Value>| {
+                        let identifier =
+                            self.current_user_widget.lock().clone().unwrap_or_default();
+                        self.try_get_current_gtk_widget_as::<gtk::ScrolledWindow>(
+                            &self.user_widgets.read(),
+                        )
+                        .unwrap()
+                        .connect_edge_reached(move |_, position| {
+                            Self::execute_callback_with_value(
+                                &identifier,
+                                &callback,
+                                &callback_arg,
+                                Self::get_str_from_position(position),
+                            );
                         });
-
-                        self.add_widget(identifier, button);
                     },
                 )
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_margin_start", move |start| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_margin_start(start)
+                .function("add_overlay", |identifier: String| {
+                    self.add_widget(identifier, gtk::Overlay::new())
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_margin_end", move |start| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
+                .function("set_overlay_alignment", |halign: String, valign: String| {
+                    let halign = Self::get_align_from_str(&halign)?;
+                    let valign = Self::get_align_from_str(&valign)?;
+                    let user_widgets = self.user_widgets.read();
+                    let widget = &self
+                        .get_current_gtk_widget(&user_widgets)
                         .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_margin_end(start)
+                        .0;
+                    if Self::get_overlay_parent(widget).is_none() {
+                        return Ok(());
+                    }
+
+                    widget.set_halign(halign);
+                    widget.set_valign(valign);
+                    Ok::<_, String>(())
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_size_request", move |width, height| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
+                .function("set_overlay_pass_through", |pass_through: bool| {
+                    let user_widgets = self.user_widgets.read();
+                    let widget = &self
+                        .get_current_gtk_widget(&user_widgets)
                         .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_size_request(width, height)
+                        .0;
+                    if Self::get_overlay_parent(widget).is_none() {
+                        return;
+                    }
+
+                    widget.set_can_target(!pass_through);
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("update_label_text", move |new_text: String| {
-                    self.try_get_current_gtk_widget_as::<gtk::Label>(&self.user_widgets.read())
-                        .unwrap()
-                        .set_text(&new_text);
+                .function("set_overlay_measure", |measure: bool| {
+                    let user_widgets = self.user_widgets.read();
+                    let widget = &self
+                        .get_current_gtk_widget(&user_widgets)
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0;
+                    if let Some(overlay) = Self::get_overlay_parent(widget) {
+                        overlay.set_measure_overlay(widget, measure);
+                    }
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("update_button_text", move |new_text: String| {
-                    self.try_get_current_gtk_widget_as::<gtk::Button>(&self.user_widgets.read())
-                        .unwrap()
-                        .set_label(&new_text)
-                })
+                .function(
+                    "add_popover",
+                    |identifier: String, parent_identifier: String| {
+                        self.add_popover(identifier, &parent_identifier, gtk::Popover::new())
+                    },
+                )
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_halign", |align: String| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_halign(self.get_align_from_str(align.as_str()));
+                .function(
+                    "add_menu",
+                    |identifier: String,
+                     parent_identifier: String,
+                     entries: Vec<Value>,
+                     callback_arg: Option<Value>| {
+                        let action_group = gtk::gio::SimpleActionGroup::new();
+                        let menu = Self::build_menu_model(
+                            &identifier,
+                            entries,
+                            &action_group,
+                            &callback_arg,
+                        )?;
+                        let popover_menu = gtk::PopoverMenu::from_model(Some(&menu));
+                        popover_menu.insert_action_group("menu", Some(&action_group));
+                        Ok::<_, String>(self.add_popover(
+                            identifier,
+                            &parent_identifier,
+                            popover_menu,
+                        ))
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("show_popover", |identifier: String| {
+                    if let Some(popover) = self.get_popover(&identifier) {
+                        popover.popup();
+                    }
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_valign", |align: String| {
-                    self.get_current_gtk_widget(&self.user_widgets.read())
-                        .expect("[ERROR] Couldn't get the current widget!")
-                        .0
-                        .set_valign(self.get_align_from_str(align.as_str()));
+                .function("hide_popover", |identifier: String| {
+                    if let Some(popover) = self.get_popover(&identifier) {
+                        popover.popdown();
+                    }
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("add_vertical_slider", |identifier, min, max, step| {
-                    self.add_widget(
-                        identifier,
-                        gtk::Scale::with_range(gtk::Orientation::Vertical, min, max, step),
-                    )
+                .function("toggle_popover", |identifier: String| {
+                    let Some(popover) = self.get_popover(&identifier) else {
+                        return;
+                    };
+
+                    if popover.is_visible() {
+                        popover.popdown();
+                    } else {
+                        popover.popup();
+                    }
                 })
                 .build()
                 .unwrap();
 
             gtk_module
                 .function(
-                    "add_horizontal_slider",
-                    |identifier: String,
-                     (min, max),
-                     step,
-                     callback: Function,
-                     callback_arg: Option<Value>| {
-                        let widget =
-                            gtk::Scale::with_range(gtk::Orientation::Horizontal, min, max, step);
-                        let identifier_clone = identifier.to_owned();
-
-                        widget.connect_value_changed(move |_| {
-                            Self::execute_callback(&identifier_clone, &callback, &callback_arg);
+                    "toggle_popover_on_click",
+                    |identifier: String, mouse_button: u32| {
+                        let Some(popover) = self.get_popover(&identifier) else {
+                            return;
+                        };
+
+                        let Some(parent) = popover.parent() else {
+                            eprintln!("[ERROR] Popover \"{identifier}\" has no parent widget!");
+                            return;
+                        };
+
+                        let gesture = gtk::GestureClick::new();
+                        gesture.set_button(mouse_button);
+                        let popover = popover.downgrade();
+                        gesture.connect_pressed(move |_, _, _, _| {
+                            let Some(popover) = popover.upgrade() else {
+                                return;
+                            };
+
+                            if popover.is_visible() {
+                                popover.popdown();
+                            } else {
+                                popover.popup();
+                            }
                         });
-
-                        self.add_widget(identifier, widget)
+                        parent.add_controller(gesture);
                     },
                 )
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("get_slider_value", move || {
-                    self.try_get_current_gtk_widget_as::<gtk::Scale>(&self.user_widgets.read())
-                        .unwrap()
-                        .value()
+                .function("remove_widget", |identifier: String| {
+                    self.remove_widget(&identifier)
                 })
                 .build()
                 .unwrap();
 
             gtk_module
-                .function("set_slider_value", move |value| {
-                    self.try_get_current_gtk_widget_as::<gtk::Scale>(&self.user_widgets.read())
-                        .unwrap()
-                        .set_value(value)
+                .function("clear_children", |identifier: String| {
+                    self.clear_children(&identifier)
                 })
                 .build()
                 .unwrap();
 
-            script_engine.assign_ui_modules(vec![
-                gtk_module,
-                LayerShellCrate::build(application_window, script_relative_path),
-                FileSystemCrate::build(),
-            ]);
-            Self::compile_source(Arc::clone(&script_engine), &script_data);
-            script_engine
-                .call_on_ui_pre_init()
-                .expect("[ERROR] VM failed calling on_ui_pre_init!");
+            gtk_module
+                .function(
+                    "move_widget",
+                    |identifier: String, new_parent: String, index: usize| {
+                        self.move_widget(&identifier, &new_parent, index)
+                    },
+                )
+                .build()
+                .unwrap();
 
-            // Add the root box widget, bypassing set_focused_widget's safety checks only this one
-            // time.
-            let widget = gtk::Box::new(gtk::Orientation::Vertical, 0);
-            application_window.0.set_child(Some(&widget));
-            self.user_widgets
-                .try_write()
-                .expect("[ERROR] user_widgets is locked, cannot add root box widget!")
-                .insert("root".to_owned(), SafeGTKWidget(widget.into()));
+            gtk_module
+                .function("insert_widget_at", |index: usize| {
+                    *self.insertion_index.lock() = Some(index);
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("reorder_after", |identifier: String, sibling: String| {
+                    self.reorder_after(&identifier, &sibling)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("load_ui_file", |path: String, parent_identifier: String| {
+                    self.load_ui_file(&path, &parent_identifier)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_children", |identifier: String| {
+                    self.get_child_identifiers(&identifier)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_parent", |identifier: String| {
+                    self.get_parent_identifier(&identifier)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("widget_exists", |identifier: String| {
+                    self.user_widgets.read().contains_key(&identifier)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("list_widgets", || self.list_widget_identifiers())
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("find_by_css_class", |class: String| {
+                    let identifiers = self.list_widget_identifiers();
+                    let user_widgets = self.user_widgets.read();
+                    identifiers
+                        .into_iter()
+                        .filter(|identifier| {
+                            user_widgets
+                                .get(identifier)
+                                .is_some_and(|widget| widget.0.has_css_class(&class))
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("build", |tree: Value| TreeBuilder::build(self, tree))
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("add_label", |identifier: String, text: String| {
+                    self.add_widget(identifier, gtk::Label::new(Some(&text)))
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_visible", |visible| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_visible(visible)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_opacity", |opacity| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_opacity(opacity)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_hexpand", |expand| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_hexpand(expand)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_vexpand", |expand| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_vexpand(expand)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_gtk_widget_name", |name: String| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_widget_name(&name)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("add_css_class", |class: String| {
+                    let widget = self
+                        .get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .clone();
+                    widget.add_css_class(&class)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("remove_css_class", |class: String| {
+                    let widget = self
+                        .get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .clone();
+                    widget.remove_css_class(&class)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("has_css_class", |class: String| {
+                    let widget = self
+                        .get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .clone();
+                    widget.has_css_class(&class)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_css_classes", |classes: Vec<String>| {
+                    let widget = self
+                        .get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .clone();
+                    let classes: Vec<&str> = classes.iter().map(String::as_str).collect();
+                    widget.set_css_classes(&classes)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("toggle_css_class", |class: String| {
+                    let widget = self
+                        .get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .clone();
+                    let enabled = !widget.has_css_class(&class);
+                    if enabled {
+                        widget.add_css_class(&class);
+                    } else {
+                        widget.remove_css_class(&class);
+                    }
+
+                    enabled
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "load_css_string",
+                    |identifier: String, css: String, priority: Option<String>| {
+                        self.load_css_provider(identifier, &css, priority.as_deref())
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "load_css_file",
+                    |identifier: String, path: String, priority: Option<String>| {
+                        let css = std::fs::read_to_string(&path).map_err(|error| {
+                            format!("Failed reading stylesheet \"{path}\", error: {error}")
+                        })?;
+                        self.load_css_provider(identifier, &css, priority.as_deref())
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "set_css_priority",
+                    |identifier: String, priority: String| {
+                        self.set_css_provider_priority(&identifier, &priority)
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("remove_css", |identifier: String| {
+                    let Some(css_provider) = self.css_providers.lock().remove(&identifier) else {
+                        eprintln!("[ERROR] No CSS has been loaded as \"{identifier}\"!");
+                        return false;
+                    };
+
+                    gtk::style_context_remove_provider_for_display(
+                        &Display::default().expect("[ERROR] Couldn't connect to a display!"),
+                        &css_provider.0,
+                    );
+                    true
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_can_target", |can_target: bool| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_can_target(can_target)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_tooltip_text", |text: Option<String>| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_tooltip_text(text.as_deref())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_tooltip_markup", |text: Option<String>| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_tooltip_markup(text.as_deref())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_width", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .width()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_width_request", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .width_request()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_width_request", |width| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_width_request(width)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_height", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .height()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_height_request", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .height_request()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_opacity", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .opacity()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("can_focus", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .can_focus()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("is_focus", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .is_focus()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("has_focus", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .has_focus()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("can_target", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .can_target()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("is_visible", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .is_visible()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("is_focusable", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .is_focusable()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("scale_factor", || {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .scale_factor()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_button",
+                    move |identifier: String,
+                          label: String,
+                          callback: Function,
+                          callback_arg: Option<Value>| {
+                        let button = gtk::Button::with_label(&label);
+                        Self::connect_state_callback(
+                            &identifier,
+                            button.upcast_ref(),
+                            callback,
+                            callback_arg,
+                        );
+                        self.add_widget(identifier, button)
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_entry",
+                    move |identifier: String,
+                          placeholder: Option<String>,
+                          on_changed: Option<Function>,
+                          on_activate: Option<Function>,
+                          callback_arg: Option<Value>| {
+                        let entry = gtk::Entry::new();
+                        entry.set_placeholder_text(placeholder.as_deref());
+                        self.add_entry(identifier, entry, on_changed, on_activate, callback_arg)
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_password_entry",
+                    move |identifier: String,
+                          placeholder: Option<String>,
+                          on_changed: Option<Function>,
+                          on_activate: Option<Function>,
+                          callback_arg: Option<Value>| {
+                        let entry = gtk::PasswordEntry::new();
+                        entry.set_placeholder_text(placeholder.as_deref());
+                        entry.set_show_peek_icon(true);
+                        self.add_entry(identifier, entry, on_changed, on_activate, callback_arg)
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_search_entry",
+                    move |identifier: String,
+                          placeholder: Option<String>,
+                          on_changed: Option<Function>,
+                          on_activate: Option<Function>,
+                          callback_arg: Option<Value>| {
+                        let entry = gtk::SearchEntry::new();
+                        entry.set_placeholder_text(placeholder.as_deref());
+                        self.add_entry(identifier, entry, on_changed, on_activate, callback_arg)
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_entry_text", move || {
+                    let text = self
+                        .get_current_gtk_widget(&self.user_widgets.read())
+                        .and_then(|widget| widget.0.dynamic_cast_ref::<gtk::Editable>())
+                        .ok_or("The current widget isn't an entry!")?
+                        .text();
+                    Ok::<_, String>(text.to_string())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_entry_text", move |text: String| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .and_then(|widget| widget.0.dynamic_cast_ref::<gtk::Editable>())
+                        .ok_or("The current widget isn't an entry!")?
+                        .set_text(&text);
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_image_from_file",
+                    move |identifier: String, path: String| {
+                        let texture = Self::load_texture(&path)?;
+                        let image = gtk::Image::from_paintable(Some(&texture));
+                        Ok::<_, String>(self.add_widget(identifier, image))
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_image_from_icon_name",
+                    move |identifier: String, icon_name: String| {
+                        Self::check_icon_exists(&icon_name)?;
+                        let image = gtk::Image::from_icon_name(&icon_name);
+                        Ok::<_, String>(self.add_widget(identifier, image))
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("add_picture", move |identifier: String, path: String| {
+                    let texture = Self::load_texture(&path)?;
+                    let picture = gtk::Picture::for_paintable(&texture);
+                    Ok::<_, String>(self.add_widget(identifier, picture))
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("update_image_file", move |path: String| {
+                    let texture = Self::load_texture(&path)?;
+                    self.try_get_current_gtk_widget_as::<gtk::Image>(&self.user_widgets.read())
+                        .ok_or("The current widget isn't an image!")?
+                        .set_from_paintable(Some(&texture));
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("update_image_icon_name", move |icon_name: String| {
+                    Self::check_icon_exists(&icon_name)?;
+                    self.try_get_current_gtk_widget_as::<gtk::Image>(&self.user_widgets.read())
+                        .ok_or("The current widget isn't an image!")?
+                        .set_icon_name(Some(&icon_name));
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_image_pixel_size", move |pixel_size| {
+                    self.try_get_current_gtk_widget_as::<gtk::Image>(&self.user_widgets.read())
+                        .ok_or("The current widget isn't an image!")?
+                        .set_pixel_size(pixel_size);
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("update_picture_file", move |path: String| {
+                    let texture = Self::load_texture(&path)?;
+                    self.try_get_current_gtk_widget_as::<gtk::Picture>(&self.user_widgets.read())
+                        .ok_or("The current widget isn't a picture!")?
+                        .set_paintable(Some(&texture));
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_check_button",
+                    move |identifier: String,
+                          label: String,
+                          callback: Function,
+                          callback_arg: Option<Value>| {
+                        let check_button = gtk::CheckButton::with_label(&label);
+                        Self::connect_state_callback(
+                            &identifier,
+                            check_button.upcast_ref(),
+                            callback,
+                            callback_arg,
+                        );
+                        self.add_widget(identifier, check_button)
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_toggle_button",
+                    move |identifier: String,
+                          label: String,
+                          callback: Function,
+                          callback_arg: Option<Value>| {
+                        let toggle_button = gtk::ToggleButton::with_label(&label);
+                        Self::connect_state_callback(
+                            &identifier,
+                            toggle_button.upcast_ref(),
+                            callback,
+                            callback_arg,
+                        );
+                        self.add_widget(identifier, toggle_button)
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_switch",
+                    move |identifier: String, callback: Function, callback_arg: Option<Value>| {
+                        let switch = gtk::Switch::new();
+                        Self::connect_state_callback(
+                            &identifier,
+                            switch.upcast_ref(),
+                            callback,
+                            callback_arg,
+                        );
+                        self.add_widget(identifier, switch)
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_active", move || {
+                    Self::get_active(
+                        &self
+                            .get_current_gtk_widget(&self.user_widgets.read())
+                            .expect("[ERROR] Couldn't get the current widget!")
+                            .0,
+                    )
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_active", move |active| {
+                    Self::set_active(
+                        &self
+                            .get_current_gtk_widget(&self.user_widgets.read())
+                            .expect("[ERROR] Couldn't get the current widget!")
+                            .0,
+                        active,
+                    )
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_check_button_group", move |group_identifier: String| {
+                    let user_widgets = self.user_widgets.read();
+                    let Some(group) = user_widgets
+                        .get(&group_identifier)
+                        .and_then(|widget| widget.0.downcast_ref::<gtk::CheckButton>())
+                    else {
+                        eprintln!(
+                            "[ERROR] No check button has been defined as \"{group_identifier}\"!"
+                        );
+                        return;
+                    };
+
+                    self.try_get_current_gtk_widget_as::<gtk::CheckButton>(&user_widgets)
+                        .unwrap()
+                        .set_group(Some(group));
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_dropdown",
+                    move |identifier: String,
+                          items: Vec<String>,
+                          callback: Function,
+                          callback_arg: Option<Value>| {
+                        let items: Vec<&str> = items.iter().map(String::as_str).collect();
+                        let dropdown = gtk::DropDown::from_strings(&items);
+                        Self::connect_state_callback(
+                            &identifier,
+                            dropdown.upcast_ref(),
+                            callback,
+                            callback_arg,
+                        );
+                        self.add_widget(identifier, dropdown)
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_dropdown_items", move |items: Vec<String>| {
+                    let user_widgets = self.user_widgets.read();
+                    let dropdown = self
+                        .try_get_current_gtk_widget_as::<gtk::DropDown>(&user_widgets)
+                        .unwrap();
+                    let Some(string_list) = dropdown
+                        .model()
+                        .and_then(|model| model.downcast::<gtk::StringList>().ok())
+                    else {
+                        eprintln!("[ERROR] The model of the current dropdown isn't a string list!");
+                        return;
+                    };
+
+                    let items: Vec<&str> = items.iter().map(String::as_str).collect();
+                    string_list.splice(0, string_list.n_items(), &items);
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_dropdown_selected_index", move || {
+                    let selected = self
+                        .try_get_current_gtk_widget_as::<gtk::DropDown>(&self.user_widgets.read())
+                        .unwrap()
+                        .selected();
+                    (selected != gtk::INVALID_LIST_POSITION).then_some(selected)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_dropdown_selected_string", move || {
+                    Self::get_dropdown_selected_string(
+                        self.try_get_current_gtk_widget_as::<gtk::DropDown>(
+                            &self.user_widgets.read(),
+                        )
+                        .unwrap(),
+                    )
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_dropdown_selected_index", move |index: u32| {
+                    self.try_get_current_gtk_widget_as::<gtk::DropDown>(&self.user_widgets.read())
+                        .unwrap()
+                        .set_selected(index)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_progress_bar",
+                    move |identifier: String, orientation: String| {
+                        let progress_bar = gtk::ProgressBar::new();
+                        progress_bar.set_orientation(Self::get_orientation_from_str(&orientation)?);
+                        Ok::<_, String>(self.add_widget(identifier, progress_bar))
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_progress_fraction", move |fraction| {
+                    self.try_get_current_gtk_widget_as::<gtk::ProgressBar>(
+                        &self.user_widgets.read(),
+                    )
+                    .unwrap()
+                    .set_fraction(fraction)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_progress_fraction", move || {
+                    self.try_get_current_gtk_widget_as::<gtk::ProgressBar>(
+                        &self.user_widgets.read(),
+                    )
+                    .unwrap()
+                    .fraction()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_progress_text", move |text: Option<String>| {
+                    let user_widgets = self.user_widgets.read();
+                    let progress_bar = self
+                        .try_get_current_gtk_widget_as::<gtk::ProgressBar>(&user_widgets)
+                        .unwrap();
+                    progress_bar.set_show_text(text.is_some());
+                    progress_bar.set_text(text.as_deref());
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_progress_pulse_step", move |pulse_step| {
+                    self.try_get_current_gtk_widget_as::<gtk::ProgressBar>(
+                        &self.user_widgets.read(),
+                    )
+                    .unwrap()
+                    .set_pulse_step(pulse_step)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("pulse_progress_bar", move || {
+                    self.try_get_current_gtk_widget_as::<gtk::ProgressBar>(
+                        &self.user_widgets.read(),
+                    )
+                    .unwrap()
+                    .pulse()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_level_bar",
+                    move |identifier: String, orientation: String, (min, max)| {
+                        let level_bar = gtk::LevelBar::for_interval(min, max);
+                        level_bar.set_orientation(Self::get_orientation_from_str(&orientation)?);
+                        Ok::<_, String>(self.add_widget(identifier, level_bar))
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_level_bar_value", move |value| {
+                    self.try_get_current_gtk_widget_as::<gtk::LevelBar>(&self.user_widgets.read())
+                        .unwrap()
+                        .set_value(value)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_level_bar_value", move || {
+                    self.try_get_current_gtk_widget_as::<gtk::LevelBar>(&self.user_widgets.read())
+                        .unwrap()
+                        .value()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_level_bar_offset", move |name: String, value| {
+                    self.try_get_current_gtk_widget_as::<gtk::LevelBar>(&self.user_widgets.read())
+                        .unwrap()
+                        .add_offset_value(&name, value)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("remove_level_bar_offset", move |name: String| {
+                    self.try_get_current_gtk_widget_as::<gtk::LevelBar>(&self.user_widgets.read())
+                        .unwrap()
+                        .remove_offset_value(Some(&name))
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_margin_start", move |start| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_margin_start(start)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_margin_end", move |start| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_margin_end(start)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_size_request", move |width, height| {
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_size_request(width, height)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("update_label_text", move |new_text: String| {
+                    self.try_get_current_gtk_widget_as::<gtk::Label>(&self.user_widgets.read())
+                        .unwrap()
+                        .set_text(&new_text);
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("update_label_markup", move |markup: String| {
+                    gtk::pango::parse_markup(&markup, '\0')
+                        .map_err(|error| format!("Invalid markup \"{markup}\": {error}"))?;
+                    self.try_get_current_gtk_widget_as::<gtk::Label>(&self.user_widgets.read())
+                        .ok_or("The current widget isn't a label!")?
+                        .set_markup(&markup);
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_label_text", move || {
+                    self.try_get_current_gtk_widget_as::<gtk::Label>(&self.user_widgets.read())
+                        .map(|label| label.text().to_string())
+                        .ok_or("The current widget isn't a label!".to_owned())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_label_wrap", move |mode: String| {
+                    let wrap_mode = Self::get_wrap_mode_from_str(&mode)?;
+                    let user_widgets = self.user_widgets.read();
+                    let label = self
+                        .try_get_current_gtk_widget_as::<gtk::Label>(&user_widgets)
+                        .ok_or("The current widget isn't a label!")?;
+                    label.set_wrap(wrap_mode.is_some());
+                    if let Some(wrap_mode) = wrap_mode {
+                        label.set_wrap_mode(wrap_mode);
+                    }
+
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_label_ellipsize", move |mode: String| {
+                    let ellipsize_mode = Self::get_ellipsize_mode_from_str(&mode)?;
+                    self.try_get_current_gtk_widget_as::<gtk::Label>(&self.user_widgets.read())
+                        .ok_or("The current widget isn't a label!")?
+                        .set_ellipsize(ellipsize_mode);
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_label_max_width_chars", move |max_width_chars| {
+                    self.try_get_current_gtk_widget_as::<gtk::Label>(&self.user_widgets.read())
+                        .ok_or("The current widget isn't a label!")?
+                        .set_max_width_chars(max_width_chars);
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_label_justify", move |justify: String| {
+                    let justification = Self::get_justification_from_str(&justify)?;
+                    self.try_get_current_gtk_widget_as::<gtk::Label>(&self.user_widgets.read())
+                        .ok_or("The current widget isn't a label!")?
+                        .set_justify(justification);
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_label_xalign", move |xalign: f64| {
+                    self.try_get_current_gtk_widget_as::<gtk::Label>(&self.user_widgets.read())
+                        .ok_or("The current widget isn't a label!")?
+                        .set_xalign(xalign.clamp(0.0, 1.0) as f32);
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_label_selectable", move |selectable| {
+                    self.try_get_current_gtk_widget_as::<gtk::Label>(&self.user_widgets.read())
+                        .ok_or("The current widget isn't a label!")?
+                        .set_selectable(selectable);
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("update_button_text", move |new_text: String| {
+                    self.try_get_current_gtk_widget_as::<gtk::Button>(&self.user_widgets.read())
+                        .unwrap()
+                        .set_label(&new_text)
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_halign", |align: String| {
+                    let align = Self::get_align_from_str(&align)?;
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_halign(align);
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_valign", |align: String| {
+                    let align = Self::get_align_from_str(&align)?;
+                    self.get_current_gtk_widget(&self.user_widgets.read())
+                        .expect("[ERROR] Couldn't get the current widget!")
+                        .0
+                        .set_valign(align);
+                    Ok::<_, String>(())
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("add_vertical_slider", |identifier, min, max, step| {
+                    self.add_widget(
+                        identifier,
+                        gtk::Scale::with_range(gtk::Orientation::Vertical, min, max, step),
+                    )
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function(
+                    "add_horizontal_slider",
+                    |identifier: String,
+                     (min, max),
+                     step,
+                     callback: Function,
+                     callback_arg: Option<Value>| {
+                        let widget =
+                            gtk::Scale::with_range(gtk::Orientation::Horizontal, min, max, step);
+                        Self::connect_state_callback(
+                            &identifier,
+                            widget.upcast_ref(),
+                            callback,
+                            callback_arg,
+                        );
+                        self.add_widget(identifier, widget)
+                    },
+                )
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("get_slider_value", move || {
+                    self.try_get_current_gtk_widget_as::<gtk::Scale>(&self.user_widgets.read())
+                        .unwrap()
+                        .value()
+                })
+                .build()
+                .unwrap();
+
+            gtk_module
+                .function("set_slider_value", move |value| {
+                    self.try_get_current_gtk_widget_as::<gtk::Scale>(&self.user_widgets.read())
+                        .unwrap()
+                        .set_value(value)
+                })
+                .build()
+                .unwrap();
+
+            WidgetHandle::register(&mut gtk_module, self);
+            script_engine.assign_ui_modules(vec![
+                gtk_module,
+                LayerShellCrate::build(self, script_relative_path),
+                FileSystemCrate::build(),
+            ]);
+            Self::compile_source(Arc::clone(&script_engine), &script_data);
+            script_engine
+                .call_on_ui_pre_init()
+                .expect("[ERROR] VM failed calling on_ui_pre_init!");
+
+            self.add_root_widget("root", &application_window);
             self.set_focused_widget("root".to_owned());
+            script_engine
+                .call_main()
+                .expect("[ERROR] VM failed calling main!");
+            if self.monitor_windows.lock().is_none() {
+                application_window.present();
+            }
+
+            Display::default()
+                .expect("[ERROR] Couldn't connect to a display!")
+                .monitors()
+                .connect_items_changed(|_, _, _, _| self.sync_monitor_windows());
+            self.watch_script(script_relative_path);
+        });
+
+        app.run();
+    }
+
+    /// Adds the root box widget named `identifier` to `window`, replacing the previous one if
+    /// present.
+    fn add_root_widget(&self, identifier: &str, window: &ApplicationWindow) {
+        let widget = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        window.set_child(Some(&widget));
+        self.user_widgets
+            .try_write()
+            .expect("[ERROR] user_widgets is locked, cannot add root box widget!")
+            .insert(identifier.to_owned(), SafeGTKWidget(widget.into()));
+    }
+
+    /// Takes the index set by `insert_widget_at`, so that it only applies to the next added widget.
+    fn take_insertion_index(&self) -> Option<usize> {
+        self.insertion_index.lock().take()
+    }
+
+    /// Gets the window targeted by the window and layer-shell functions.
+    fn get_current_window(&self) -> ApplicationWindow {
+        let current_window = self.current_window.lock();
+        self.windows
+            .read()
+            .get(current_window.as_str())
+            .map(|window| window.0.to_owned())
+            .expect("[ERROR] Couldn't get the current window!")
+    }
+
+    /// Gets the window whose root box widget is named `identifier`.
+    fn get_window(&self, identifier: &str) -> Option<ApplicationWindow> {
+        let window = self
+            .windows
+            .read()
+            .get(identifier)
+            .map(|window| window.0.to_owned());
+        if window.is_none() {
+            eprintln!("[ERROR] No window has been created as \"{identifier}\"!");
+        }
+
+        window
+    }
+
+    /// Creates a hidden window with a root box widget named `identifier`, returning a handle to
+    /// the root box.
+    /// If the window already exists, a handle to its root box is returned instead.
+    fn create_window(&self, identifier: String) -> Option<WidgetHandle> {
+        if self.take_insertion_index().is_some() {
+            eprintln!("[WARN] Windows cannot be inserted at an index, ignoring it!");
+        }
+
+        if self.windows.read().contains_key(&identifier) {
+            return Some(WidgetHandle::new(identifier));
+        }
+
+        if self.user_widgets.read().contains_key(&identifier) {
+            eprintln!("[ERROR] There is already a widget named \"{identifier}\"!");
+            return None;
+        }
+
+        let window = ApplicationWindow::builder()
+            .application(
+                &self
+                    .get_window("root")?
+                    .application()
+                    .expect("[ERROR] The main window has no application!"),
+            )
+            .hide_on_close(true)
+            .build();
+        self.add_root_widget(&identifier, &window);
+        self.windows
+            .write()
+            .insert(identifier.to_owned(), SafeApplicationWindow(window));
+        Some(WidgetHandle::new(identifier))
+    }
+
+    /// Destroys the window whose root box widget is named `identifier`, forgetting all of the
+    /// user widgets inside of it.
+    /// The main window cannot be destroyed.
+    fn destroy_window(&self, identifier: &str) -> bool {
+        if identifier == "root" {
+            eprintln!("[ERROR] The main window cannot be destroyed!");
+            return false;
+        }
+
+        let Some(window) = self.windows.write().remove(identifier) else {
+            eprintln!("[ERROR] No window has been created as \"{identifier}\"!");
+            return false;
+        };
+
+        if let Some(root) = window.0.child() {
+            let mut removed_widgets = Vec::new();
+            self.user_widgets.write().retain(|_, user_widget| {
+                if user_widget.0 != root && !user_widget.0.is_ancestor(&root) {
+                    return true;
+                }
+
+                removed_widgets.push(user_widget.0.clone());
+                false
+            });
+            Self::release_removed_widgets(&removed_widgets);
+        }
+
+        let mut current_user_widget = self.current_user_widget.lock();
+        if current_user_widget
+            .as_ref()
+            .is_some_and(|current| !self.user_widgets.read().contains_key(current))
+        {
+            *current_user_widget = Some("root".to_owned());
+        }
+
+        drop(current_user_widget);
+        let mut current_window = self.current_window.lock();
+        if current_window.as_str() == identifier {
+            *current_window = "root".to_owned();
+        }
+
+        if let Some(monitor_windows) = self.monitor_windows.lock().as_mut() {
+            monitor_windows.retain(|connector| connector != identifier);
+        }
+
+        #[cfg(feature = "x11")]
+        self.x11_docks.lock().remove(identifier);
+        self.click_through_windows.lock().remove(identifier);
+
+        window.0.destroy();
+        true
+    }
+
+    /// Makes the current window let input pass through to whatever is below it if
+    /// `click_through` is `true`, by giving its surface an empty input region.
+    fn set_click_through(&'static self, click_through: bool) {
+        let identifier = self.current_window.lock().to_owned();
+        let window = self.get_current_window();
+        let previous = self
+            .click_through_windows
+            .lock()
+            .insert(identifier.to_owned(), click_through);
+
+        if previous.is_none() {
+            match window.surface() {
+                Some(surface) => self.keep_input_region(identifier, &window, &surface),
+                None => {
+                    window.connect_realize(move |window| {
+                        if let Some(surface) = window.surface() {
+                            self.keep_input_region(identifier.to_owned(), window, &surface);
+                        }
+                    });
+                }
+            }
+        }
+
+        if click_through || previous == Some(true) {
+            Self::set_input_region(&window, click_through);
+        }
+    }
+
+    /// Keeps the input region of the window named `identifier` empty whenever it's laid out while
+    /// it's click-through, as GTK resets it for client-side decorated windows.
+    fn keep_input_region(
+        &'static self,
+        identifier: String,
+        window: &ApplicationWindow,
+        surface: &gtk::gdk::Surface,
+    ) {
+        let window = window.to_owned();
+        surface.connect_layout(move |_, _, _| {
+            let click_through = self.click_through_windows.lock().get(&identifier).copied();
+            if click_through == Some(true) {
+                Self::set_input_region(&window, true);
+            }
+        });
+    }
+
+    /// Sets the input region of the surface of `window` to nothing if `click_through` is `true`,
+    /// or unsets it otherwise, so that GTK manages it again.
+    /// Does nothing if `window` hasn't been realized yet.
+    fn set_input_region(window: &ApplicationWindow, click_through: bool) {
+        let Some(surface) = window.surface() else {
+            return;
+        };
+
+        if click_through {
+            surface.set_input_region(&gtk::cairo::Region::create());
+        } else {
+            // `Surface::set_input_region` doesn't accept `NULL`, which restores the default.
+            unsafe {
+                gtk::gdk::ffi::gdk_surface_set_input_region(surface.as_ptr(), std::ptr::null_mut());
+            }
+
+            window.queue_resize();
+        }
+    }
+
+    /// Gets all monitors of the default display.
+    fn get_monitors() -> Vec<gtk::gdk::Monitor> {
+        let monitors = Display::default()
+            .expect("[ERROR] Couldn't connect to a display!")
+            .monitors();
+        (0..monitors.n_items())
+            .filter_map(|index| monitors.item(index))
+            .filter_map(|monitor| monitor.downcast::<gtk::gdk::Monitor>().ok())
+            .collect()
+    }
+
+    /// Gets the monitor whose connector is named `connector`, such as `DP-1`.
+    fn get_monitor(connector: &str) -> Option<gtk::gdk::Monitor> {
+        Self::get_monitors()
+            .into_iter()
+            .find(|monitor| monitor.connector().as_deref() == Some(connector))
+    }
+
+    /// Creates a window for every newly connected monitor and destroys the windows of
+    /// disconnected monitors, if windows are being created per monitor.
+    /// Each window is named after the connector of its monitor, and is built by calling the
+    /// `on_monitor_added` and `on_monitor_removed` Rune functions with the event argument and the
+    /// connector name.
+    fn sync_monitor_windows(&self) {
+        let connectors: Vec<String> = Self::get_monitors()
+            .iter()
+            .filter_map(|monitor| monitor.connector().map(|connector| connector.into()))
+            .collect();
+
+        let (removed, added): (Vec<String>, Vec<String>) = {
+            let monitor_windows = self.monitor_windows.lock();
+            let Some(monitor_windows) = monitor_windows.as_ref() else {
+                return;
+            };
+
+            (
+                monitor_windows
+                    .iter()
+                    .filter(|connector| !connectors.contains(connector))
+                    .cloned()
+                    .collect(),
+                connectors
+                    .iter()
+                    .filter(|connector| !monitor_windows.contains(connector))
+                    .cloned()
+                    .collect(),
+            )
+        };
+
+        for connector in removed {
+            self.call_monitor_hook("on_monitor_removed", &connector);
+            self.destroy_window(&connector);
+        }
+
+        let previous_window = self.current_window.lock().to_owned();
+        let previous_widget = self.current_user_widget.lock().to_owned();
+        for connector in added {
+            if self.create_window(connector.to_owned()).is_none() {
+                continue;
+            }
+
+            if let Some(monitor_windows) = self.monitor_windows.lock().as_mut() {
+                monitor_windows.push(connector.to_owned());
+            }
+
+            *self.current_window.lock() = connector.to_owned();
+            self.set_focused_widget(connector.to_owned());
+            self.call_monitor_hook("on_monitor_added", &connector);
+
+            let (Some(window), Some(monitor)) =
+                (self.get_window(&connector), Self::get_monitor(&connector))
+            else {
+                continue;
+            };
+
+            if window.is_layer_window() {
+                window.set_monitor(&monitor);
+            }
+
+            #[cfg(feature = "x11")]
+            if let Some(dock) = self.x11_docks.lock().get_mut(&connector) {
+                dock.0.monitor = Some(monitor);
+                dock.0.apply(&window);
+            }
+
+            window.present();
+        }
+
+        if self.windows.read().contains_key(&previous_window) {
+            *self.current_window.lock() = previous_window;
+        }
+
+        if let Some(previous_widget) = previous_widget {
+            if self.user_widgets.read().contains_key(&previous_widget) {
+                self.set_focused_widget(previous_widget);
+            }
+        }
+    }
+
+    /// Calls the Rune function named `name` with the `connector` of a monitor, if defined.
+    fn call_monitor_hook(&self, name: &str, connector: &str) {
+        let script_engine = self
+            .script_engine
+            .get()
+            .expect("[ERROR] No stored Script Engine!");
+        if !script_engine.has_function(name) {
+            return;
+        }
+
+        if let Err(error) = script_engine.call_function_by_name(name, connector) {
+            eprintln!("[ERROR] {error}");
+        }
+    }
+
+    /// Watches the script at `script_path`, reloading it whenever it changes.
+    fn watch_script(&'static self, script_path: &'static str) {
+        self.watch_file(script_path, move || self.reload_script(script_path));
+    }
+
+    /// Watches the file at `path`, calling `on_changed` whenever it changes.
+    /// Changes made within `FILE_CHANGE_DELAY` of each other are merged into a single call, as
+    /// editors saving atomically emit several events per save.
+    fn watch_file(&self, path: &str, on_changed: impl Fn() + 'static) {
+        let monitor = match gtk::gio::File::for_path(path).monitor_file(
+            gtk::gio::FileMonitorFlags::WATCH_MOVES,
+            gtk::gio::Cancellable::NONE,
+        ) {
+            Ok(monitor) => monitor,
+            Err(error) => {
+                eprintln!("[ERROR] Failed watching \"{path}\", error: {error}");
+                return;
+            }
+        };
+
+        let on_changed = Rc::new(on_changed);
+        let pending_change: Rc<Cell<Option<gtk::glib::SourceId>>> = Rc::default();
+        monitor.connect_changed(move |_, _, _, event| {
+            if !matches!(
+                event,
+                gtk::gio::FileMonitorEvent::ChangesDoneHint
+                    | gtk::gio::FileMonitorEvent::Created
+                    | gtk::gio::FileMonitorEvent::MovedIn
+                    | gtk::gio::FileMonitorEvent::Renamed
+            ) {
+                return;
+            }
+
+            if let Some(pending_change) = pending_change.take() {
+                pending_change.remove();
+            }
+
+            let on_changed = Rc::clone(&on_changed);
+            let pending_change_clone = Rc::clone(&pending_change);
+            pending_change.set(Some(gtk::glib::timeout_add_local_once(
+                FILE_CHANGE_DELAY,
+                move || {
+                    pending_change_clone.set(None);
+                    on_changed();
+                },
+            )));
+        });
+
+        if let Some(previous_monitor) = self
+            .file_monitors
+            .lock()
+            .insert(path.to_owned(), SafeFileMonitor(monitor))
+        {
+            previous_monitor.0.cancel();
+        }
+    }
+
+    /// Recompiles the script at `script_path`, tears down the current UI and builds it again by
+    /// calling `on_ui_pre_init` and `main`.
+    /// If the script fails to compile, its diagnostics are printed and the current UI stays live.
+    fn reload_script(&self, script_path: &str) {
+        let script_data = match std::fs::read_to_string(script_path) {
+            Ok(script_data) => script_data,
+            Err(error) => {
+                eprintln!("[ERROR] Failed reading script \"{script_path}\", error: {error}");
+                return;
+            }
+        };
+
+        let script_engine = Arc::clone(
+            self.script_engine
+                .get()
+                .expect("[ERROR] No stored Script Engine!"),
+        );
+        if let Err(error) = Arc::clone(&script_engine).run_from_input(&script_data) {
+            eprintln!(
+                "[ERROR] Failed compiling \"{script_path}\", keeping the previous UI! Error: {error}"
+            );
+            return;
+        }
+
+        println!("[INFO] Reloading \"{script_path}\"");
+        self.reset_ui();
+        if let Err(error) = script_engine.call_on_ui_pre_init() {
+            eprintln!("[ERROR] VM failed calling on_ui_pre_init, error: {error}");
+        }
+
+        let Some(main_window) = self.get_window("root") else {
+            return;
+        };
+
+        self.add_root_widget("root", &main_window);
+        self.set_focused_widget("root".to_owned());
+        if let Err(error) = script_engine.call_main() {
+            eprintln!("[ERROR] VM failed calling main, error: {error}");
+        }
+    }
+
+    /// Forgets all user widgets, destroys all windows but the main one and removes the CSS loaded
+    /// by the script, so that the UI can be built from scratch.
+    fn reset_ui(&self) {
+        let removed_widgets: Vec<Widget> = self
+            .user_widgets
+            .try_write()
+            .expect("[ERROR] user_widgets is locked, cannot reset the UI!")
+            .drain()
+            .map(|(_, user_widget)| user_widget.0)
+            .collect();
+        Self::release_removed_widgets(&removed_widgets);
+        *self.current_user_widget.lock() = None;
+        *self.insertion_index.lock() = None;
+
+        self.windows.write().retain(|identifier, window| {
+            if identifier == "root" {
+                return true;
+            }
+
+            window.0.destroy();
+            false
+        });
+        *self.current_window.lock() = "root".to_owned();
+        *self.monitor_windows.lock() = None;
+        #[cfg(feature = "x11")]
+        self.x11_docks
+            .lock()
+            .retain(|identifier, _| identifier == "root");
+        let was_click_through = self
+            .click_through_windows
+            .lock()
+            .get_mut("root")
+            .is_some_and(|click_through| std::mem::replace(click_through, false));
+        if let Some(main_window) = self.get_window("root").filter(|_| was_click_through) {
+            Self::set_input_region(&main_window, false);
+        }
+
+        let display = Display::default().expect("[ERROR] Couldn't connect to a display!");
+        for (_, css_provider) in self.css_providers.lock().drain() {
+            gtk::style_context_remove_provider_for_display(&display, &css_provider.0);
+        }
+    }
+
+    /// Gets the `gtk::Align` value from the `align` string-slice.
+    fn get_align_from_str(align: &str) -> Result<gtk::Align, String> {
+        match align {
+            "Start" => Ok(gtk::Align::Start),
+            "Center" => Ok(gtk::Align::Center),
+            "End" => Ok(gtk::Align::End),
+            "Fill" => Ok(gtk::Align::Fill),
+            _ => Err(format!(
+                "Invalid alignment \"{align}\", use Start, Center, Fill or End!"
+            )),
+        }
+    }
+
+    /// Gets the active state of a `gtk::CheckButton`, `gtk::ToggleButton` or `gtk::Switch`.
+    fn get_active(widget: &Widget) -> Option<bool> {
+        if let Some(check_button) = widget.downcast_ref::<gtk::CheckButton>() {
+            return Some(check_button.is_active());
+        }
+
+        if let Some(toggle_button) = widget.downcast_ref::<gtk::ToggleButton>() {
+            return Some(toggle_button.is_active());
+        }
+
+        if let Some(switch) = widget.downcast_ref::<gtk::Switch>() {
+            return Some(switch.is_active());
+        }
+
+        eprintln!("[ERROR] The current widget doesn't have an active state!");
+        None
+    }
+
+    /// Sets the active state of a `gtk::CheckButton`, `gtk::ToggleButton` or `gtk::Switch`.
+    fn set_active(widget: &Widget, active: bool) {
+        if let Some(check_button) = widget.downcast_ref::<gtk::CheckButton>() {
+            check_button.set_active(active);
+        } else if let Some(toggle_button) = widget.downcast_ref::<gtk::ToggleButton>() {
+            toggle_button.set_active(active);
+        } else if let Some(switch) = widget.downcast_ref::<gtk::Switch>() {
+            switch.set_active(active);
+        } else {
+            eprintln!("[ERROR] The current widget doesn't have an active state!");
+        }
+    }
+
+    /// Gets the currently selected string of a `gtk::DropDown` backed by a `gtk::StringList`.
+    fn get_dropdown_selected_string(dropdown: &gtk::DropDown) -> Option<String> {
+        dropdown
+            .selected_item()
+            .and_then(|item| item.downcast::<gtk::StringObject>().ok())
+            .map(|item| item.string().to_string())
+    }
+
+    /// Loads the image at `path` into a texture, returning a readable error if it fails.
+    fn load_texture(path: &str) -> Result<gtk::gdk::Texture, String> {
+        gtk::gdk::Texture::from_filename(path)
+            .map_err(|error| format!("Failed loading image \"{path}\", error: {error}"))
+    }
+
+    /// Checks if `icon_name` is present in the icon theme of the default display.
+    fn check_icon_exists(icon_name: &str) -> Result<(), String> {
+        let display = Display::default().expect("[ERROR] Couldn't connect to a display!");
+        if !gtk::IconTheme::for_display(&display).has_icon(icon_name) {
+            return Err(format!(
+                "No icon named \"{icon_name}\" in the current icon theme!"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Gets the `gtk::Orientation` value from the `orientation` string-slice.
+    fn get_orientation_from_str(orientation: &str) -> Result<gtk::Orientation, String> {
+        match orientation {
+            "Horizontal" => Ok(gtk::Orientation::Horizontal),
+            "Vertical" => Ok(gtk::Orientation::Vertical),
+            _ => Err(format!(
+                "Invalid orientation \"{orientation}\", use Horizontal or Vertical!"
+            )),
+        }
+    }
+
+    /// Gets the `gtk::PolicyType` value from the `policy` string-slice.
+    fn get_policy_from_str(policy: &str) -> Result<gtk::PolicyType, String> {
+        match policy {
+            "Always" => Ok(gtk::PolicyType::Always),
+            "Automatic" => Ok(gtk::PolicyType::Automatic),
+            "Never" => Ok(gtk::PolicyType::Never),
+            "External" => Ok(gtk::PolicyType::External),
+            _ => Err(format!(
+                "Invalid scrollbar policy \"{policy}\", use Always, Automatic, Never or External!"
+            )),
+        }
+    }
+
+    /// Gets the `pango::WrapMode` value from the `mode` string-slice, or `None` if wrapping should
+    /// be disabled.
+    fn get_wrap_mode_from_str(mode: &str) -> Result<Option<gtk::pango::WrapMode>, String> {
+        match mode {
+            "None" => Ok(None),
+            "Word" => Ok(Some(gtk::pango::WrapMode::Word)),
+            "Char" => Ok(Some(gtk::pango::WrapMode::Char)),
+            "WordChar" => Ok(Some(gtk::pango::WrapMode::WordChar)),
+            _ => Err(format!(
+                "Invalid wrap mode \"{mode}\", use None, Word, Char or WordChar!"
+            )),
+        }
+    }
+
+    /// Gets the `pango::EllipsizeMode` value from the `mode` string-slice.
+    fn get_ellipsize_mode_from_str(mode: &str) -> Result<gtk::pango::EllipsizeMode, String> {
+        match mode {
+            "None" => Ok(gtk::pango::EllipsizeMode::None),
+            "Start" => Ok(gtk::pango::EllipsizeMode::Start),
+            "Middle" => Ok(gtk::pango::EllipsizeMode::Middle),
+            "End" => Ok(gtk::pango::EllipsizeMode::End),
+            _ => Err(format!(
+                "Invalid ellipsize mode \"{mode}\", use None, Start, Middle or End!"
+            )),
+        }
+    }
+
+    /// Gets the `gtk::Justification` value from the `justify` string-slice.
+    fn get_justification_from_str(justify: &str) -> Result<gtk::Justification, String> {
+        match justify {
+            "Left" => Ok(gtk::Justification::Left),
+            "Right" => Ok(gtk::Justification::Right),
+            "Center" => Ok(gtk::Justification::Center),
+            "Fill" => Ok(gtk::Justification::Fill),
+            _ => Err(format!(
+                "Invalid justification \"{justify}\", use Left, Right, Center or Fill!"
+            )),
+        }
+    }
+
+    /// Gets the string representation of `position`, as passed into Rune callbacks.
+    fn get_str_from_position(position: gtk::PositionType) -> &'static str {
+        match position {
+            gtk::PositionType::Left => "Left",
+            gtk::PositionType::Right => "Right",
+            gtk::PositionType::Top => "Top",
+            _ => "Bottom",
+        }
+    }
+
+    /// Loads custom CSS from the `STYLESHEET` environment variable, if defined.
+    fn load_css(&self) {
+        let Ok(stylesheet_path) = std::env::var("STYLESHEET") else {
+            return;
+        };
+
+        // Load the CSS file and add it to the provider
+        let provider = CssProvider::new();
+        let source = stylesheet_path.to_owned();
+        provider.connect_parsing_error(move |_, section, error| {
+            Self::report_css_error(&source, section, error)
+        });
+        provider.load_from_string(
+            &std::fs::read_to_string(&stylesheet_path)
+                .expect("[ERROR] Failed reading custom stylesheet!"),
+        );
+
+        // Add the provider to the default screen
+        gtk::style_context_add_provider_for_display(
+            &Display::default().expect("[ERROR] Couldn't connect to a display!"),
+            &provider,
+            gtk::STYLE_PROVIDER_PRIORITY_USER,
+        );
+
+        // Reload the provider in place whenever the stylesheet changes
+        let path = stylesheet_path.to_owned();
+        self.watch_file(&stylesheet_path, move || {
+            Self::reload_stylesheet(&provider, &path)
+        });
+    }
+
+    /// Reloads `provider` from the stylesheet at `path`.
+    /// If the new stylesheet fails to parse, its errors are reported and the previous one is
+    /// kept active.
+    fn reload_stylesheet(provider: &CssProvider, path: &str) {
+        let css = match std::fs::read_to_string(path) {
+            Ok(css) => css,
+            Err(error) => {
+                eprintln!("[ERROR] Failed reading stylesheet \"{path}\", error: {error}");
+                return;
+            }
+        };
+
+        // Parse into a scratch provider first, so that errors don't affect the active stylesheet
+        let has_errors = Rc::new(Cell::new(false));
+        let scratch_provider = CssProvider::new();
+        let source = path.to_owned();
+        let has_errors_clone = Rc::clone(&has_errors);
+        scratch_provider.connect_parsing_error(move |_, section, error| {
+            Self::report_css_error(&source, section, error);
+            has_errors_clone.set(true);
+        });
+        scratch_provider.load_from_string(&css);
+
+        if has_errors.get() {
+            eprintln!("[ERROR] Keeping the previous stylesheet, as \"{path}\" has errors!");
+            return;
+        }
+
+        provider.load_from_string(&css);
+        println!("[INFO] Reloaded stylesheet \"{path}\"");
+    }
+
+    /// Reports a CSS parsing `error` from `source` at the location of `section`.
+    fn report_css_error(source: &str, section: &gtk::CssSection, error: &gtk::glib::Error) {
+        let location = section.start_location();
+        eprintln!(
+            "[ERROR] {source}:{}:{}: {error}",
+            location.lines() + 1,
+            location.line_chars() + 1
+        );
+    }
+
+    /// Loads `css` into the provider named `identifier`, creating it at user priority if it
+    /// doesn't exist yet.
+    /// If `priority` is set, the provider is moved to the style provider priority it names.
+    fn load_css_provider(
+        &self,
+        identifier: String,
+        css: &str,
+        priority: Option<&str>,
+    ) -> Result<(), String> {
+        let priority = priority.map(Self::get_css_priority_from_str).transpose()?;
+        let display = Display::default().expect("[ERROR] Couldn't connect to a display!");
+        let mut css_providers = self.css_providers.lock();
+        let css_provider = match css_providers.entry(identifier) {
+            Entry::Occupied(entry) => {
+                let css_provider = entry.into_mut();
+                if let Some(priority) = priority {
+                    gtk::style_context_remove_provider_for_display(&display, &css_provider.0);
+                    gtk::style_context_add_provider_for_display(
+                        &display,
+                        &css_provider.0,
+                        priority,
+                    );
+                }
+
+                css_provider
+            }
+            Entry::Vacant(entry) => {
+                let provider = CssProvider::new();
+                let identifier = entry.key().to_owned();
+                provider.connect_parsing_error(move |_, section, error| {
+                    Self::report_css_error(&identifier, section, error)
+                });
+
+                gtk::style_context_add_provider_for_display(
+                    &display,
+                    &provider,
+                    priority.unwrap_or(gtk::STYLE_PROVIDER_PRIORITY_USER),
+                );
+                entry.insert(SafeCssProvider(provider))
+            }
+        };
+
+        css_provider.0.load_from_string(css);
+        Ok(())
+    }
+
+    /// Moves the provider named `identifier` to the style provider priority named `priority`.
+    fn set_css_provider_priority(&self, identifier: &str, priority: &str) -> Result<(), String> {
+        let priority = Self::get_css_priority_from_str(priority)?;
+        let css_providers = self.css_providers.lock();
+        let css_provider = css_providers
+            .get(identifier)
+            .ok_or_else(|| format!("No CSS has been loaded as \"{identifier}\"!"))?;
+
+        let display = Display::default().expect("[ERROR] Couldn't connect to a display!");
+        gtk::style_context_remove_provider_for_display(&display, &css_provider.0);
+        gtk::style_context_add_provider_for_display(&display, &css_provider.0, priority);
+        Ok(())
+    }
+
+    /// Gets the style provider priority from the `priority` string-slice.
+    fn get_css_priority_from_str(priority: &str) -> Result<u32, String> {
+        match priority {
+            "Fallback" => Ok(gtk::STYLE_PROVIDER_PRIORITY_FALLBACK),
+            "Theme" => Ok(gtk::STYLE_PROVIDER_PRIORITY_THEME),
+            "Settings" => Ok(gtk::STYLE_PROVIDER_PRIORITY_SETTINGS),
+            "Application" => Ok(gtk::STYLE_PROVIDER_PRIORITY_APPLICATION),
+            "User" => Ok(gtk::STYLE_PROVIDER_PRIORITY_USER),
+            _ => Err(format!(
+                "Invalid priority \"{priority}\", use Fallback, Theme, Settings, Application or User!"
+            )),
+        }
+    }
+
+    /// Executes the given callback function, with `callback_arg` if set.
+    fn execute_callback(identifier: &str, callback: &Function, callback_arg: &Option<Value>) {
+        let result: Result<(), rune::runtime::VmError> =
+            if let Some(callback_arg) = callback_arg.as_ref() {
+                callback.call::<_, ()>((callback_arg,)).into_result()
+            } else {
+                callback.call::<_, ()>(()).into_result()
+            };
+
+        Self::check_callback_result(identifier, result);
+    }
+
+    /// Executes the given callback function with `value`, prefixed by `callback_arg` if set.
+    fn execute_callback_with_value<V: ToValue>(
+        identifier: &str,
+        callback: &Function,
+        callback_arg: &Option<Value>,
+        value: V,
+    ) {
+        let result: Result<(), rune::runtime::VmError> =
+            if let Some(callback_arg) = callback_arg.as_ref() {
+                callback.call::<_, ()>((callback_arg, value)).into_result()
+            } else {
+                callback.call::<_, ()>((value,)).into_result()
+            };
+
+        Self::check_callback_result(identifier, result);
+    }
+
+    /// Panics if calling the callback function of `identifier` failed.
+    fn check_callback_result(identifier: &str, result: Result<(), rune::runtime::VmError>) {
+        if let Err(error) = result {
+            // rust-analyzer is fucking terrible and can't format this if it's
+            // all in one string.
+            panic!(
+                "[ERROR] Failed calling callback function of {}",
+                format_args!("\"{identifier}\", error: {error}")
+            );
+        }
+    }
+
+    /// Adds a new widget to the UI, returning a handle to it if successful.
+    fn add_widget<W: gtk::prelude::IsA<gtk::Widget>>(
+        &self,
+        identifier: String,
+        widget: W,
+    ) -> Option<WidgetHandle> {
+        let index = self.take_insertion_index();
+        if !self.can_add_widgets_to_current() {
+            eprintln!("[ERROR] Cannot add widgets into a widget that can't hold child widgets!");
+            return None;
+        }
+
+        let Some(mut user_widgets) = self.user_widgets.try_write() else {
+            eprintln!("[ERROR] user_widgets is locked, cannot add widget!");
+            return None;
+        };
+
+        if user_widgets.contains_key(&identifier) {
+            eprintln!("[ERROR] Widget \"{identifier}\" already exists!");
+            return None;
+        }
+
+        let Some(container) = self.get_current_gtk_widget(&user_widgets) else {
+            eprintln!("[ERROR] Couldn't get the current widget!");
+            return None;
+        };
+
+        if !Self::add_child_to_container(&container.0, &identifier, widget.upcast_ref(), index) {
+            return None;
+        }
+
+        widget.set_widget_name(&identifier);
+        self.connect_enter_exit_events(identifier.to_owned(), &widget);
+        user_widgets.insert(identifier.to_owned(), SafeGTKWidget(widget.into()));
+        drop(user_widgets); // Release lock.
+        Some(WidgetHandle::new(identifier))
+    }
+
+    /// Adds a new text entry widget to the UI, connecting its change and activate callbacks.
+    fn add_entry<E: IsA<gtk::Widget> + IsA<gtk::Editable>>(
+        &self,
+        identifier: String,
+        entry: E,
+        on_changed: Option<Function>,
+        on_activate: Option<Function>,
+        callback_arg: Option<Value>,
+    ) -> Option<WidgetHandle> {
+        Self::connect_entry_callbacks(&identifier, &entry, on_changed, on_activate, callback_arg);
+        self.add_widget(identifier, entry)
+    }
+
+    /// Connects the change and activate callbacks of a text entry widget.
+    /// Both callbacks are called with the current text of the entry.
+    fn connect_entry_callbacks<E: IsA<gtk::Editable>>(
+        identifier: &str,
+        entry: &E,
+        on_changed: Option<Function>,
+        on_activate: Option<Function>,
+        callback_arg: Option<Value>,
+    ) {
+        if let Some(on_changed) = on_changed {
+            let identifier_clone = identifier.to_owned();
+            let callback_arg = callback_arg.clone();
+            entry.connect_changed(move |entry| {
+                Self::execute_callback_with_value(
+                    &identifier_clone,
+                    &on_changed,
+                    &callback_arg,
+                    entry.text().to_string(),
+                );
+            });
+        }
+
+        if let Some(on_activate) = on_activate {
+            let identifier_clone = identifier.to_owned();
+            entry.connect_local("activate", false, move |values| {
+                let entry = values[0]
+                    .get::<gtk::Editable>()
+                    .expect("[ERROR] activate was emitted by a non-editable widget!");
+                Self::execute_callback_with_value(
+                    &identifier_clone,
+                    &on_activate,
+                    &callback_arg,
+                    entry.text().to_string(),
+                );
+                None
+            });
+        }
+    }
+
+    /// Connects `callback` to the signal emitted when the state of `widget` changes, such as
+    /// `clicked` for buttons.
+    /// Widgets holding a state (toggles, dropdowns) pass it into the callback after
+    /// `callback_arg`.
+    fn connect_state_callback(
+        identifier: &str,
+        widget: &Widget,
+        callback: Function,
+        callback_arg: Option<Value>,
+    ) -> bool {
+        let identifier = identifier.to_owned();
+        if let Some(toggle_button) = widget.downcast_ref::<gtk::ToggleButton>() {
+            toggle_button.connect_toggled(move |toggle_button| {
+                Self::execute_callback_with_value(
+                    &identifier,
+                    &callback,
+                    &callback_arg,
+                    toggle_button.is_active(),
+                );
+            });
+        } else if let Some(button) = widget.downcast_ref::<gtk::Button>() {
+            button.connect_clicked(move |_| {
+                Self::execute_callback(&identifier, &callback, &callback_arg);
+            });
+        } else if let Some(check_button) = widget.downcast_ref::<gtk::CheckButton>() {
+            check_button.connect_toggled(move |check_button| {
+                Self::execute_callback_with_value(
+                    &identifier,
+                    &callback,
+                    &callback_arg,
+                    check_button.is_active(),
+                );
+            });
+        } else if let Some(switch) = widget.downcast_ref::<gtk::Switch>() {
+            switch.connect_active_notify(move |switch| {
+                Self::execute_callback_with_value(
+                    &identifier,
+                    &callback,
+                    &callback_arg,
+                    switch.is_active(),
+                );
+            });
+        } else if let Some(dropdown) = widget.downcast_ref::<gtk::DropDown>() {
+            dropdown.connect_selected_notify(move |dropdown| {
+                Self::execute_callback_with_value(
+                    &identifier,
+                    &callback,
+                    &callback_arg,
+                    Self::get_dropdown_selected_string(dropdown),
+                );
+            });
+        } else if let Some(scale) = widget.downcast_ref::<gtk::Scale>() {
+            scale.connect_value_changed(move |_| {
+                Self::execute_callback(&identifier, &callback, &callback_arg);
+            });
+        } else {
+            eprintln!(
+                "[ERROR] \"{}\" has no state callback to connect!",
+                widget.type_().name()
+            );
+            return false;
+        }
+
+        true
+    }
+
+    /// Adds a new popover to the UI, attached to the widget named `parent_identifier`, returning a
+    /// handle to it if successful.
+    fn add_popover<P: IsA<gtk::Popover> + IsA<gtk::Widget>>(
+        &self,
+        identifier: String,
+        parent_identifier: &str,
+        popover: P,
+    ) -> Option<WidgetHandle> {
+        if self.take_insertion_index().is_some() {
+            eprintln!("[WARN] Popovers cannot be inserted at an index, ignoring it!");
+        }
+
+        let Some(mut user_widgets) = self.user_widgets.try_write() else {
+            eprintln!("[ERROR] user_widgets is locked, cannot add popover!");
+            return None;
+        };
+
+        if user_widgets.contains_key(&identifier) {
+            eprintln!("[ERROR] Widget \"{identifier}\" already exists!");
+            return None;
+        }
+
+        let Some(parent) = user_widgets.get(parent_identifier) else {
+            eprintln!("[ERROR] No widget has been defined as \"{parent_identifier}\"!");
+            return None;
+        };
+
+        popover.set_parent(&parent.0);
+        popover.set_widget_name(&identifier);
+        self.connect_enter_exit_events(identifier.to_owned(), &popover);
+        user_widgets.insert(
+            identifier.to_owned(),
+            SafeGTKWidget(popover.upcast::<Widget>()),
+        );
+        drop(user_widgets); // Release lock.
+        Some(WidgetHandle::new(identifier))
+    }
+
+    /// Gets the popover named `identifier`.
+    fn get_popover(&self, identifier: &str) -> Option<gtk::Popover> {
+        let popover = self
+            .user_widgets
+            .read()
+            .get(identifier)
+            .and_then(|widget| widget.0.downcast_ref::<gtk::Popover>().cloned());
+        if popover.is_none() {
+            eprintln!("[ERROR] No popover has been defined as \"{identifier}\"!");
+        }
+
+        popover
+    }
+
+    /// Builds a menu model out of Rune menu `entries`, registering an action into `action_group`
+    /// for every entry.
+    /// Each entry is either a `(label, action_fn)` tuple, a `(label, [entries])` tuple for
+    /// submenus, or `"separator"`.
+    fn build_menu_model(
+        identifier: &str,
+        entries: Vec<Value>,
+        action_group: &gtk::gio::SimpleActionGroup,
+        callback_arg: &Option<Value>,
+    ) -> Result<gtk::gio::Menu, String> {
+        let menu = gtk::gio::Menu::new();
+        let mut section = gtk::gio::Menu::new();
+        for entry in entries {
+            if let Ok(separator) = rune::from_value::<String>(entry.clone()) {
+                if separator != "separator" {
+                    return Err(format!("Invalid menu entry \"{separator}\"!"));
+                }
+
+                menu.append_section(None, &section);
+                section = gtk::gio::Menu::new();
+                continue;
+            }
+
+            let (label, action) = rune::from_value::<(String, Value)>(entry).map_err(|_| {
+                "Menu entries must be (label, action_fn) or (label, [entries]) tuples!".to_owned()
+            })?;
+
+            if let Value::Vec(_) = action {
+                let submenu_entries = rune::from_value::<Vec<Value>>(action)
+                    .map_err(|error| format!("Invalid submenu \"{label}\", error: {error}"))?;
+                let submenu = Self::build_menu_model(
+                    identifier,
+                    submenu_entries,
+                    action_group,
+                    callback_arg,
+                )?;
+                section.append_submenu(Some(&label), &submenu);
+                continue;
+            }
+
+            let callback = rune::from_value::<Function>(action)
+                .map_err(|error| format!("Invalid action for \"{label}\", error: {error}"))?;
+            let action_name = format!("action-{}", action_group.list_actions().len());
+            let action = gtk::gio::SimpleAction::new(&action_name, None);
+            let identifier = identifier.to_owned();
+            let callback_arg = callback_arg.clone();
+            action.connect_activate(move |_, _| {
+                Self::execute_callback(&identifier, &callback, &callback_arg);
+            });
+
+            action_group.add_action(&action);
+            section.append(Some(&label), Some(&format!("menu.{action_name}")));
+        }
+
+        menu.append_section(None, &section);
+        Ok(menu)
+    }
+
+    /// Adds `child` into `container` using the container-specific API.
+    /// If `index` is set and `container` is a box or a flow box, `child` is inserted at `index`
+    /// instead of being appended.
+    /// Returns `true` if the child was added.
+    fn add_child_to_container(
+        container: &Widget,
+        identifier: &str,
+        child: &Widget,
+        index: Option<usize>,
+    ) -> bool {
+        if Self::is_container_full(container, child) {
+            eprintln!(
+                "[ERROR] \"{}\" cannot hold any more children, cannot add \"{identifier}\"!",
+                container.type_().name()
+            );
+            return false;
+        }
+
+        if let Some(box_widget) = container.downcast_ref::<gtk::Box>() {
+            match index {
+                Some(0) => box_widget.insert_child_after(child, None::<&Widget>),
+                Some(index) => match Self::get_nth_child(container, index - 1) {
+                    Some(sibling) => box_widget.insert_child_after(child, Some(&sibling)),
+                    None => box_widget.append(child),
+                },
+                None => box_widget.append(child),
+            }
+
+            return true;
+        }
+
+        if let Some(flow_box) = container.downcast_ref::<gtk::FlowBox>() {
+            match index {
+                Some(index) => flow_box.insert(child, index.try_into().unwrap_or(-1)),
+                None => flow_box.append(child),
+            }
+
+            return true;
+        }
+
+        if index.is_some() {
+            eprintln!(
+                "[WARN] \"{}\" doesn't support inserting at an index, ignoring it!",
+                container.type_().name()
+            );
+        }
 
-            script_engine
-                .call_main()
-                .expect("[ERROR] VM failed calling main!");
-            application_window.0.present();
-        });
+        if let Some(grid) = container.downcast_ref::<gtk::Grid>() {
+            grid.attach_next_to(child, None::<&Widget>, gtk::PositionType::Right, 1, 1);
+            return true;
+        }
 
-        app.run();
-    }
+        if let Some(stack) = container.downcast_ref::<gtk::Stack>() {
+            stack.add_named(child, Some(identifier));
+            return true;
+        }
 
-    /// Gets the `gtk::Align` value from the `align` string-slice.
-    fn get_align_from_str(&self, align: &str) -> gtk::Align {
-        match align {
-            "Start" => gtk::Align::Start,
-            "Center" => gtk::Align::Center,
-            "End" => gtk::Align::End,
-            "Fill" => gtk::Align::Fill,
-            _ => {
-                panic!("[ERROR] Invalid alignment, use Start, Center, Fill or End!")
+        if let Some(scrolled_window) = container.downcast_ref::<gtk::ScrolledWindow>() {
+            scrolled_window.set_child(Some(child));
+            return true;
+        }
+
+        if let Some(overlay) = container.downcast_ref::<gtk::Overlay>() {
+            if overlay.child().is_none() {
+                overlay.set_child(Some(child));
+            } else {
+                overlay.add_overlay(child);
+            }
+
+            return true;
+        }
+
+        if let Some(popover) = container
+            .downcast_ref::<gtk::Popover>()
+            .filter(|popover| !popover.is::<gtk::PopoverMenu>())
+        {
+            popover.set_child(Some(child));
+            return true;
+        }
+
+        if let Some(center_box) = container.downcast_ref::<gtk::CenterBox>() {
+            if center_box.start_widget().is_none() {
+                center_box.set_start_widget(Some(child));
+            } else if center_box.center_widget().is_none() {
+                center_box.set_center_widget(Some(child));
+            } else {
+                center_box.set_end_widget(Some(child));
             }
+
+            return true;
         }
+
+        eprintln!(
+            "[ERROR] \"{}\" cannot hold child widgets!",
+            container.type_().name()
+        );
+        false
     }
 
-    /// Loads custom CSS from the `STYLESHEET` environment variable, if defined.
-    fn load_css(&self) {
-        let Ok(stylesheet_path) = std::env::var("STYLESHEET") else {
-            return;
+    /// Checks if every slot of a single-child container or a center box is taken by a widget other
+    /// than `child`.
+    fn is_container_full(container: &Widget, child: &Widget) -> bool {
+        let is_taken = |slot: Option<Widget>| {
+            slot.is_some_and(|slot| {
+                &slot != child
+                    && slot
+                        .downcast_ref::<gtk::Viewport>()
+                        .and_then(|viewport| viewport.child())
+                        .as_ref()
+                        != Some(child)
+            })
         };
 
-        // Load the CSS file and add it to the provider
-        let provider = CssProvider::new();
-        provider.load_from_string(
-            &std::fs::read_to_string(stylesheet_path)
-                .expect("[ERROR] Failed reading custom stylesheet!"),
-        );
+        if let Some(scrolled_window) = container.downcast_ref::<gtk::ScrolledWindow>() {
+            return is_taken(scrolled_window.child());
+        }
 
-        // Add the provider to the default screen
-        gtk::style_context_add_provider_for_display(
-            &Display::default().expect("[ERROR] Couldn't connect to a display!"),
-            &provider,
-            gtk::STYLE_PROVIDER_PRIORITY_USER,
-        );
+        if let Some(popover) = container
+            .downcast_ref::<gtk::Popover>()
+            .filter(|popover| !popover.is::<gtk::PopoverMenu>())
+        {
+            return is_taken(popover.child());
+        }
+
+        if let Some(center_box) = container.downcast_ref::<gtk::CenterBox>() {
+            return is_taken(center_box.start_widget())
+                && is_taken(center_box.center_widget())
+                && is_taken(center_box.end_widget());
+        }
+
+        false
     }
 
-    /// Executes the given callback function, with `callback_arg` if set.
-    fn execute_callback(identifier: &str, callback: &Function, callback_arg: &Option<Value>) {
-        let result: Result<(), rune::runtime::VmError> =
-            if let Some(callback_arg) = callback_arg.as_ref() {
-                callback.call::<_, ()>((callback_arg,)).into_result()
-            } else {
-                callback.call::<_, ()>(()).into_result()
-            };
+    /// Gets the `n`th direct child of `widget`.
+    fn get_nth_child(widget: &Widget, n: usize) -> Option<Widget> {
+        let mut child = widget.first_child();
+        for _ in 0..n {
+            child = child?.next_sibling();
+        }
 
-        if let Err(error) = result {
-            // rust-analyzer is fucking terrible and can't format this if it's
-            // all in one string.
-            panic!(
-                "[ERROR] Failed calling callback function of {}",
-                format_args!("\"{identifier}\", error: {error}")
+        child
+    }
+
+    /// Moves the widget named `identifier` into the widget named `new_parent_identifier`,
+    /// inserting it at `index` if the new parent supports it.
+    fn move_widget(&self, identifier: &str, new_parent_identifier: &str, index: usize) -> bool {
+        let Some(user_widgets) = self.user_widgets.try_read() else {
+            eprintln!("[ERROR] user_widgets is locked, cannot move \"{identifier}\"!");
+            return false;
+        };
+
+        let Some(widget) = user_widgets.get(identifier).map(|widget| widget.0.clone()) else {
+            eprintln!("[ERROR] No widget has been defined as \"{identifier}\"!");
+            return false;
+        };
+
+        let Some(new_parent) = user_widgets
+            .get(new_parent_identifier)
+            .map(|widget| widget.0.clone())
+        else {
+            eprintln!("[ERROR] No widget has been defined as \"{new_parent_identifier}\"!");
+            return false;
+        };
+
+        drop(user_widgets); // Release lock.
+        if !Self::is_container(&new_parent) {
+            eprintln!("[ERROR] \"{new_parent_identifier}\" cannot hold child widgets!");
+            return false;
+        }
+
+        if new_parent == widget || new_parent.is_ancestor(&widget) {
+            eprintln!("[ERROR] \"{identifier}\" cannot be moved into itself!");
+            return false;
+        }
+
+        if Self::is_container_full(&new_parent, &widget) {
+            eprintln!(
+                "[ERROR] \"{new_parent_identifier}\" cannot hold any more children, cannot move \"{identifier}\"!"
             );
+            return false;
         }
+
+        let index =
+            Some(index).filter(|_| new_parent.is::<gtk::Box>() || new_parent.is::<gtk::FlowBox>());
+        Self::remove_child_from_container(&widget);
+        Self::add_child_to_container(&new_parent, identifier, &widget, index)
     }
 
-    /// Adds a new widget to the UI.
-    fn add_widget<W: gtk::prelude::IsA<gtk::Widget>>(&self, identifier: String, widget: W) {
-        if !self.can_add_widgets_to_current() {
-            eprintln!("[ERROR] Cannot add widgets into a widget that can't hold child widgets!");
-            return;
+    /// Moves the widget named `identifier` right after `sibling_identifier` inside of their shared
+    /// parent box.
+    fn reorder_after(&self, identifier: &str, sibling_identifier: &str) -> bool {
+        if identifier == sibling_identifier {
+            eprintln!("[ERROR] \"{identifier}\" cannot be reordered after itself!");
+            return false;
         }
 
-        let Some(mut user_widgets) = self.user_widgets.try_write() else {
-            eprintln!("[ERROR] user_widgets is locked, cannot add widget!");
-            return;
+        let Some(user_widgets) = self.user_widgets.try_read() else {
+            eprintln!("[ERROR] user_widgets is locked, cannot reorder \"{identifier}\"!");
+            return false;
         };
 
-        if user_widgets.contains_key(&identifier) {
-            eprintln!("[ERROR] Widget \"{identifier}\" already exists!");
-            return;
+        let (Some(widget), Some(sibling)) = (
+            user_widgets.get(identifier),
+            user_widgets.get(sibling_identifier),
+        ) else {
+            eprintln!(
+                "[ERROR] Both \"{identifier}\" and \"{sibling_identifier}\" have to be defined!"
+            );
+            return false;
+        };
+
+        let Some(box_widget) = widget
+            .0
+            .parent()
+            .filter(|parent| sibling.0.parent().as_ref() == Some(parent))
+            .and_then(|parent| parent.downcast::<gtk::Box>().ok())
+        else {
+            eprintln!(
+                "[ERROR] \"{identifier}\" and \"{sibling_identifier}\" aren't in the same box!"
+            );
+            return false;
+        };
+
+        box_widget.reorder_child_after(&widget.0, Some(&sibling.0));
+        true
+    }
+
+    /// Checks if `widget` is a container that `add_child_to_container` knows how to add children
+    /// into.
+    fn is_container(widget: &Widget) -> bool {
+        widget.is::<gtk::Box>()
+            || widget.is::<gtk::FlowBox>()
+            || widget.is::<gtk::Grid>()
+            || widget.is::<gtk::Stack>()
+            || widget.is::<gtk::CenterBox>()
+            || widget.is::<gtk::ScrolledWindow>()
+            || widget.is::<gtk::Overlay>()
+            || (widget.is::<gtk::Popover>() && !widget.is::<gtk::PopoverMenu>())
+    }
+
+    /// Gets the parent `gtk::Overlay` of `widget`, if `widget` has been added as an overlay.
+    fn get_overlay_parent(widget: &Widget) -> Option<gtk::Overlay> {
+        let overlay = widget
+            .parent()
+            .and_then(|parent| parent.downcast::<gtk::Overlay>().ok())
+            .filter(|overlay| overlay.child().as_ref() != Some(widget));
+        if overlay.is_none() {
+            eprintln!("[ERROR] The current widget isn't an overlay of an overlay container!");
         }
 
-        let downcast = self.try_get_current_gtk_widget_as::<gtk::Box>(&user_widgets);
-        if let Some(box_widget) = downcast {
-            widget.set_widget_name(&identifier);
-            self.connect_enter_exit_events(identifier.to_owned(), &widget);
-            box_widget.append(&widget);
-            user_widgets.insert(identifier, SafeGTKWidget(widget.into()));
-            drop(user_widgets); // Release lock.
-            return;
+        overlay
+    }
+
+    /// Places `widget` into the given `slot` of its parent `gtk::CenterBox`.
+    fn set_center_box_slot(widget: &Widget, slot: &str) -> bool {
+        let Some(center_box) = widget
+            .parent()
+            .and_then(|parent| parent.downcast::<gtk::CenterBox>().ok())
+        else {
+            eprintln!("[ERROR] The parent of the current widget isn't a center box!");
+            return false;
+        };
+
+        let slot_widget = match slot {
+            "Start" => center_box.start_widget(),
+            "Center" => center_box.center_widget(),
+            "End" => center_box.end_widget(),
+            _ => {
+                eprintln!("[ERROR] Invalid center box slot, use Start, Center or End!");
+                return false;
+            }
+        };
+
+        if slot_widget.is_some_and(|slot_widget| &slot_widget != widget) {
+            eprintln!("[ERROR] The \"{slot}\" slot of the center box is already taken!");
+            return false;
         }
 
-        eprintln!(
-            "[ERROR] Failed casting widget as \"{}\"!",
-            std::any::type_name::<W>()
-        );
+        if center_box.start_widget().as_ref() == Some(widget) {
+            center_box.set_start_widget(None::<&Widget>);
+        } else if center_box.center_widget().as_ref() == Some(widget) {
+            center_box.set_center_widget(None::<&Widget>);
+        } else if center_box.end_widget().as_ref() == Some(widget) {
+            center_box.set_end_widget(None::<&Widget>);
+        }
+
+        match slot {
+            "Start" => center_box.set_start_widget(Some(widget)),
+            "Center" => center_box.set_center_widget(Some(widget)),
+            _ => center_box.set_end_widget(Some(widget)),
+        }
+
+        true
     }
 
     /// Connects the Enter and Exit events for a widget, into Rune.
@@ -601,6 +3123,7 @@ impl UIBuilder {
         let identifier_clone = identifier.to_owned();
         let script_engine_clone = Arc::clone(&script_engine);
         let motion_controller = gtk::EventControllerMotion::new();
+        motion_controller.set_name(Some(ENTER_EXIT_CONTROLLER_NAME));
         motion_controller.connect_enter(move |_, _, _| {
             script_engine_clone.call_enter_exit(&identifier_clone, true);
         });
@@ -613,6 +3136,394 @@ impl UIBuilder {
         widget.add_controller(motion_controller);
     }
 
+    /// Removes the motion controllers added by `connect_enter_exit_events` from `widget`.
+    fn disconnect_enter_exit_events(widget: &Widget) {
+        let controllers = widget.observe_controllers();
+        let enter_exit_controllers: Vec<gtk::EventController> = (0..controllers.n_items())
+            .filter_map(|index| controllers.item(index))
+            .filter_map(|controller| controller.downcast::<gtk::EventController>().ok())
+            .filter(|controller| controller.name().as_deref() == Some(ENTER_EXIT_CONTROLLER_NAME))
+            .collect();
+
+        for controller in enter_exit_controllers {
+            widget.remove_controller(&controller);
+        }
+    }
+
+    /// Removes the widget named `identifier` and all of its descendants from the UI.
+    fn remove_widget(&self, identifier: &str) -> bool {
+        if self.windows.read().contains_key(identifier) {
+            eprintln!("[ERROR] The root widget of a window cannot be removed!");
+            return false;
+        }
+
+        let Some(mut user_widgets) = self.user_widgets.try_write() else {
+            eprintln!("[ERROR] user_widgets is locked, cannot remove \"{identifier}\"!");
+            return false;
+        };
+
+        let Some(widget) = user_widgets.get(identifier).map(|widget| widget.0.clone()) else {
+            eprintln!("[ERROR] No widget has been defined as \"{identifier}\"!");
+            return false;
+        };
+
+        let parent_identifier =
+            Self::get_registered_parent(&Self::get_widget_identifiers(&user_widgets), &widget);
+        let removed_identifiers: Vec<String> = user_widgets
+            .iter()
+            .filter(|(_, user_widget)| {
+                user_widget.0 == widget || user_widget.0.is_ancestor(&widget)
+            })
+            .map(|(identifier, _)| identifier.to_owned())
+            .collect();
+        let removed_widgets: Vec<Widget> = removed_identifiers
+            .iter()
+            .filter_map(|identifier| user_widgets.remove(identifier))
+            .map(|user_widget| user_widget.0)
+            .collect();
+        drop(user_widgets); // Release lock.
+
+        let Some(mut current_user_widget) = self.current_user_widget.try_lock() else {
+            eprintln!("[ERROR] current_user_widget is locked, cannot remove \"{identifier}\"!");
+            return false;
+        };
+
+        if current_user_widget
+            .as_ref()
+            .is_some_and(|current| removed_identifiers.contains(current))
+        {
+            *current_user_widget = parent_identifier;
+        }
+
+        drop(current_user_widget); // Release lock.
+        Self::release_removed_widgets(&removed_widgets);
+        Self::remove_child_from_container(&widget);
+        true
+    }
+
+    /// Disconnects the enter/exit events of the removed user widgets, and unparents the popovers
+    /// among them, as they aren't dropped along with the widget they're attached to.
+    fn release_removed_widgets(removed_widgets: &[Widget]) {
+        for removed_widget in removed_widgets {
+            Self::disconnect_enter_exit_events(removed_widget);
+            if removed_widget.is::<gtk::Popover>() {
+                removed_widget.unparent();
+            }
+        }
+    }
+
+    /// Removes all child widgets of the widget named `identifier`, keeping the widget itself.
+    /// Returns `false` if any of the children couldn't be removed.
+    fn clear_children(&self, identifier: &str) -> bool {
+        if self.get_user_widget(identifier).is_none() {
+            return false;
+        }
+
+        let mut removed_all = true;
+        for child in self.get_child_identifiers(identifier) {
+            removed_all &= self.remove_widget(&child);
+        }
+
+        removed_all
+    }
+
+    /// Gets a reference to the user widget named `identifier`.
+    fn get_user_widget(&self, identifier: &str) -> Option<Widget> {
+        let Some(user_widgets) = self.user_widgets.try_read() else {
+            eprintln!("[ERROR] user_widgets is locked, cannot get \"{identifier}\"!");
+            return None;
+        };
+
+        let widget = user_widgets.get(identifier).map(|widget| widget.0.clone());
+        if widget.is_none() {
+            eprintln!("[ERROR] No widget has been defined as \"{identifier}\"!");
+        }
+
+        widget
+    }
+
+    /// Loads the GtkBuilder `.ui` file at `path`, attaching its top-level widgets under the widget
+    /// named `parent_identifier` and registering every widget with an `id` as a user widget.
+    /// Signal handlers declared in the file are resolved to Rune functions by name, and called
+    /// with the event argument and the identifier of the object which emitted the signal.
+    fn load_ui_file(&self, path: &str, parent_identifier: &str) -> Result<(), String> {
+        let index = self.take_insertion_index();
+        let ui_data = std::fs::read_to_string(path)
+            .map_err(|error| format!("Failed reading \"{path}\", error: {error}"))?;
+        let parent = self
+            .get_user_widget(parent_identifier)
+            .ok_or_else(|| format!("No widget has been defined as \"{parent_identifier}\"!"))?;
+        if !Self::is_container(&parent) {
+            return Err(format!(
+                "\"{parent_identifier}\" cannot hold child widgets!"
+            ));
+        }
+
+        let script_engine = self
+            .script_engine
+            .get()
+            .expect("[ERROR] No stored Script Engine!");
+        let scope = ScriptScope::new(Arc::clone(script_engine));
+        let builder = gtk::Builder::new();
+        builder.set_scope(Some(&scope));
+        builder
+            .add_from_string(&ui_data)
+            .map_err(|error| format!("Failed building \"{path}\", error: {error}"))?;
+
+        let widgets: Vec<(String, Widget)> = builder
+            .objects()
+            .into_iter()
+            .filter_map(|object| object.downcast::<Widget>().ok())
+            .filter(|widget| !widget.is::<gtk::Window>())
+            .filter_map(|widget| {
+                let identifier = widget.buildable_id()?.to_string();
+                Some((identifier, widget))
+            })
+            .collect();
+
+        let Some(user_widgets) = self.user_widgets.try_read() else {
+            return Err("user_widgets is locked, cannot load the UI file!".to_owned());
+        };
+
+        if let Some((identifier, _)) = widgets
+            .iter()
+            .find(|(identifier, _)| user_widgets.contains_key(identifier))
+        {
+            return Err(format!("Widget \"{identifier}\" already exists!"));
+        }
+
+        drop(user_widgets); // Release lock.
+        let mut attached_widgets = Vec::new();
+        for object in builder.objects() {
+            let Ok(widget) = object.downcast::<Widget>() else {
+                continue;
+            };
+
+            if widget.parent().is_some() || widget.is::<gtk::Popover>() {
+                continue;
+            }
+
+            let widget = match widget.downcast::<gtk::Window>() {
+                Ok(window) => {
+                    let Some(child) = window.child() else {
+                        continue;
+                    };
+
+                    window.set_child(None::<&Widget>);
+                    window.destroy();
+                    child
+                }
+                Err(widget) => widget,
+            };
+
+            let identifier = widget.buildable_id().unwrap_or_default();
+            let child_index = index.map(|index| index + attached_widgets.len());
+            if !Self::add_child_to_container(&parent, &identifier, &widget, child_index) {
+                for attached_widget in &attached_widgets {
+                    Self::remove_child_from_container(attached_widget);
+                }
+
+                return Err(format!(
+                    "Failed attaching the widgets of \"{path}\" to \"{parent_identifier}\"!"
+                ));
+            }
+
+            attached_widgets.push(widget);
+        }
+
+        let Some(mut user_widgets) = self.user_widgets.try_write() else {
+            for attached_widget in &attached_widgets {
+                Self::remove_child_from_container(attached_widget);
+            }
+
+            return Err("user_widgets is locked, cannot load the UI file!".to_owned());
+        };
+
+        for (identifier, widget) in &widgets {
+            if widget.widget_name() == widget.type_().name() {
+                widget.set_widget_name(identifier);
+            }
+
+            self.connect_enter_exit_events(identifier.to_owned(), widget);
+            user_widgets.insert(identifier.to_owned(), SafeGTKWidget(widget.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// Gets the identifiers of the user widgets whose closest user widget ancestor is the widget
+    /// named `identifier`, in the order they appear in.
+    fn get_child_identifiers(&self, identifier: &str) -> Vec<String> {
+        let Some(user_widgets) = self.user_widgets.try_read() else {
+            eprintln!("[ERROR] user_widgets is locked, cannot get children of \"{identifier}\"!");
+            return Vec::new();
+        };
+
+        let mut children = Vec::new();
+        if let Some(widget) = user_widgets.get(identifier) {
+            let widget_identifiers = Self::get_widget_identifiers(&user_widgets);
+            Self::collect_registered_children(&widget_identifiers, &widget.0, false, &mut children);
+        }
+
+        children
+    }
+
+    /// Gets the identifier of the closest user widget ancestor of the widget named `identifier`.
+    fn get_parent_identifier(&self, identifier: &str) -> Option<String> {
+        let widget = self.get_user_widget(identifier)?;
+        let user_widgets = self.user_widgets.read();
+        Self::get_registered_parent(&Self::get_widget_identifiers(&user_widgets), &widget)
+    }
+
+    /// Gets the identifiers of all user widgets, in the order they appear in, starting with the
+    /// root widget of the main window, followed by the root widgets of the other windows.
+    fn list_widget_identifiers(&self) -> Vec<String> {
+        let Some(user_widgets) = self.user_widgets.try_read() else {
+            eprintln!("[ERROR] user_widgets is locked, cannot list widgets!");
+            return Vec::new();
+        };
+
+        let mut roots: Vec<String> = self
+            .windows
+            .read()
+            .keys()
+            .filter(|identifier| identifier.as_str() != "root")
+            .cloned()
+            .collect();
+        roots.sort_unstable();
+        roots.insert(0, "root".to_owned());
+
+        let widget_identifiers = Self::get_widget_identifiers(&user_widgets);
+        let mut identifiers = Vec::new();
+        for root in roots {
+            let Some(widget) = user_widgets.get(&root) else {
+                continue;
+            };
+
+            identifiers.push(root.to_owned());
+            Self::collect_registered_children(
+                &widget_identifiers,
+                &widget.0,
+                true,
+                &mut identifiers,
+            );
+        }
+
+        identifiers
+    }
+
+    /// Pushes the identifiers of the closest user widget descendants of `widget` into
+    /// `identifiers`, in the order they appear in.
+    /// If `recursive` is `true`, the descendants of those user widgets are pushed too.
+    fn collect_registered_children(
+        widget_identifiers: &HashMap<Widget, &str>,
+        widget: &Widget,
+        recursive: bool,
+        identifiers: &mut Vec<String>,
+    ) {
+        let mut child = widget.first_child();
+        while let Some(current) = child {
+            match widget_identifiers.get(&current) {
+                Some(identifier) => {
+                    identifiers.push(identifier.to_string());
+                    if recursive {
+                        Self::collect_registered_children(
+                            widget_identifiers,
+                            &current,
+                            recursive,
+                            identifiers,
+                        );
+                    }
+                }
+                None => Self::collect_registered_children(
+                    widget_identifiers,
+                    &current,
+                    recursive,
+                    identifiers,
+                ),
+            }
+
+            child = current.next_sibling();
+        }
+    }
+
+    /// Maps every user widget to its identifier, so that GTK nodes can be looked up while walking
+    /// the widget tree.
+    fn get_widget_identifiers(
+        user_widgets: &HashMap<String, SafeGTKWidget>,
+    ) -> HashMap<Widget, &str> {
+        user_widgets
+            .iter()
+            .map(|(identifier, user_widget)| (user_widget.0.clone(), identifier.as_str()))
+            .collect()
+    }
+
+    /// Gets the identifier of the closest ancestor of `widget` that is a user widget.
+    fn get_registered_parent(
+        widget_identifiers: &HashMap<Widget, &str>,
+        widget: &Widget,
+    ) -> Option<String> {
+        let mut ancestor = widget.parent();
+        while let Some(current) = ancestor {
+            if let Some(identifier) = widget_identifiers.get(&current) {
+                return Some(identifier.to_string());
+            }
+
+            ancestor = current.parent();
+        }
+
+        None
+    }
+
+    /// Removes `widget` from its parent using the container-specific API.
+    fn remove_child_from_container(widget: &Widget) {
+        let Some(parent) = widget.parent() else {
+            return;
+        };
+
+        if widget.is::<gtk::Popover>() {
+            widget.unparent();
+        } else if let Some(box_widget) = parent.downcast_ref::<gtk::Box>() {
+            box_widget.remove(widget);
+        } else if let Some(flow_box) = parent
+            .parent()
+            .filter(|_| parent.is::<gtk::FlowBoxChild>())
+            .and_then(|flow_box| flow_box.downcast::<gtk::FlowBox>().ok())
+        {
+            flow_box.remove(widget);
+        } else if let Some(grid) = parent.downcast_ref::<gtk::Grid>() {
+            grid.remove(widget);
+        } else if let Some(stack) = parent.downcast_ref::<gtk::Stack>() {
+            stack.remove(widget);
+        } else if let Some(scrolled_window) = parent
+            .parent()
+            .filter(|_| parent.is::<gtk::Viewport>())
+            .and_then(|scrolled_window| scrolled_window.downcast::<gtk::ScrolledWindow>().ok())
+        {
+            scrolled_window.set_child(None::<&Widget>);
+        } else if let Some(scrolled_window) = parent.downcast_ref::<gtk::ScrolledWindow>() {
+            scrolled_window.set_child(None::<&Widget>);
+        } else if let Some(overlay) = parent.downcast_ref::<gtk::Overlay>() {
+            if overlay.child().as_ref() == Some(widget) {
+                overlay.set_child(None::<&Widget>);
+            } else {
+                overlay.remove_overlay(widget);
+            }
+        } else if let Some(popover) = parent.downcast_ref::<gtk::Popover>() {
+            popover.set_child(None::<&Widget>);
+        } else if let Some(center_box) = parent.downcast_ref::<gtk::CenterBox>() {
+            if center_box.start_widget().as_ref() == Some(widget) {
+                center_box.set_start_widget(None::<&Widget>);
+            } else if center_box.center_widget().as_ref() == Some(widget) {
+                center_box.set_center_widget(None::<&Widget>);
+            } else {
+                center_box.set_end_widget(None::<&Widget>);
+            }
+        } else {
+            widget.unparent();
+        }
+    }
+
     /// Switches focus from one widget to another.
     fn set_focused_widget(&self, identifier: String) -> bool {
         let Some(user_widgets) = self.user_widgets.try_read() else {
@@ -674,18 +3585,6 @@ impl UIBuilder {
         Some(casted_widget)
     }
 
-    /// Checks if the current widget can be casted to the desired widget type.
-    /// Shortcut for using `try_get_current_gtk_widget_as::<W>(...).is_ok()`.
-    fn can_cast_current_widget_to<W: gtk::prelude::IsA<gtk::Widget>>(&self) -> bool {
-        let Some(user_widgets) = self.user_widgets.try_read() else {
-            eprintln!("[ERROR] user_widgets is locked, cannot use can_cast_current_widget_to!");
-            return false;
-        };
-
-        self.try_get_current_gtk_widget_as::<W>(&user_widgets)
-            .is_some()
-    }
-
     /// Gets the current GTK Widget wrapped inside of `SafeGTKWidget`.
     fn get_current_gtk_widget<'a>(
         &'a self,
@@ -701,7 +3600,13 @@ impl UIBuilder {
 
     /// Checks if the current widget can hold child widgets.
     fn can_add_widgets_to_current(&self) -> bool {
-        self.can_cast_current_widget_to::<gtk::Box>()
+        let Some(user_widgets) = self.user_widgets.try_read() else {
+            eprintln!("[ERROR] user_widgets is locked, cannot use can_add_widgets_to_current!");
+            return false;
+        };
+
+        self.get_current_gtk_widget(&user_widgets)
+            .is_some_and(|widget| Self::is_container(&widget.0))
     }
 
     /// Compiles the `script_data` source.
@@ -0,0 +1,99 @@
+use crate::script::ScriptEngine;
+use gtk::{glib, subclass::prelude::*};
+use std::sync::Arc;
+
+glib::wrapper! {
+    /// GtkBuilder scope which resolves the signal handlers of a `.ui` file to Rune functions by
+    /// name, failing the build when no such function has been defined.
+    pub struct ScriptScope(ObjectSubclass<imp::ScriptScope>)
+        @extends gtk::BuilderCScope,
+        @implements gtk::BuilderScope;
+}
+
+impl ScriptScope {
+    pub fn new(script_engine: Arc<ScriptEngine>) -> Self {
+        let scope: Self = glib::Object::new();
+        scope
+            .imp()
+            .script_engine
+            .set(script_engine)
+            .unwrap_or_else(|_| unreachable!());
+        scope
+    }
+}
+
+mod imp {
+    use crate::script::ScriptEngine;
+    use gtk::{
+        glib::{self, Closure, RustClosure},
+        prelude::*,
+        subclass::prelude::*,
+        Builder, BuilderClosureFlags, BuilderError,
+    };
+    use rune::Value;
+    use std::{cell::OnceCell, sync::Arc};
+
+    #[derive(Default)]
+    pub struct ScriptScope {
+        pub script_engine: OnceCell<Arc<ScriptEngine>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ScriptScope {
+        const NAME: &'static str = "CrispScriptScope";
+        type Type = super::ScriptScope;
+        type ParentType = gtk::BuilderCScope;
+        type Interfaces = (gtk::BuilderScope,);
+    }
+
+    impl ObjectImpl for ScriptScope {}
+    impl BuilderCScopeImpl for ScriptScope {}
+    impl BuilderScopeImpl for ScriptScope {
+        fn type_from_function(&self, _builder: &Builder, _function_name: &str) -> glib::Type {
+            // Don't look up C symbols like `BuilderCScope` does.
+            glib::Type::INVALID
+        }
+
+        /// Creates a closure calling the Rune function named `function_name` with the event
+        /// argument and the identifier of the object which emitted the signal.
+        fn create_closure(
+            &self,
+            _builder: &Builder,
+            function_name: &str,
+            _flags: BuilderClosureFlags,
+            _object: Option<&glib::Object>,
+        ) -> Result<Closure, glib::Error> {
+            let script_engine = Arc::clone(
+                self.script_engine
+                    .get()
+                    .expect("[ERROR] No stored Script Engine!"),
+            );
+
+            if !script_engine.has_function(function_name) {
+                return Err(glib::Error::new(
+                    BuilderError::InvalidFunction,
+                    &format!("No function named \"{function_name}\" has been defined!"),
+                ));
+            }
+
+            let function_name = function_name.to_owned();
+            let closure = RustClosure::new_local(move |values| {
+                let identifier = values
+                    .first()
+                    .and_then(|value| value.get::<gtk::Buildable>().ok())
+                    .and_then(|buildable| buildable.buildable_id())
+                    .unwrap_or_default();
+                match script_engine.call_function_by_name(&function_name, &identifier) {
+                    Ok(Value::Bool(result)) => Some(result.into()),
+                    Ok(_) => None,
+                    Err(error) => {
+                        eprintln!("[ERROR] {error}");
+                        None
+                    }
+                }
+            });
+
+            Ok(closure.as_ref().clone())
+        }
+    }
+}
@@ -0,0 +1,465 @@
+use super::{widget_handle::WidgetHandle, UIBuilder};
+use gtk::{prelude::*, Widget};
+use rune::{runtime::Function, FromValue, Value};
+use std::collections::HashMap;
+
+/// Properties which every node accepts, regardless of its type.
+const COMMON_PROPERTIES: &[&str] = &[
+    "type",
+    "id",
+    "children",
+    "callback_arg",
+    "name",
+    "tooltip",
+    "visible",
+    "halign",
+    "valign",
+    "hexpand",
+    "vexpand",
+    "width",
+    "height",
+    "opacity",
+    "margin_start",
+    "margin_end",
+];
+
+/// Builds widget hierarchies out of nested Rune objects, such as:
+/// ```rune
+/// GTK::build(#{ type: "box", id: "bar", orientation: "horizontal", children: [
+///     #{ type: "label", id: "clock", text: "00:00" },
+/// ] });
+/// ```
+pub struct TreeBuilder;
+
+/// A single node of the tree being built.
+struct TreeNode {
+    /// Path of the node inside of the tree, used for error messages.
+    path: String,
+
+    /// Properties of the node.
+    properties: HashMap<String, Value>,
+}
+
+impl TreeNode {
+    /// Gets the property named `key` as `T`, if present.
+    fn get<T: FromValue>(&self, key: &str) -> Result<Option<T>, String> {
+        self.properties
+            .get(key)
+            .map(|value| {
+                rune::from_value::<T>(value.clone()).map_err(|error| {
+                    format!("{}: invalid \"{key}\" property, error: {error}", self.path)
+                })
+            })
+            .transpose()
+    }
+
+    /// Gets the property named `key` as `T`, returning an error if it isn't present.
+    fn require<T: FromValue>(&self, key: &str) -> Result<T, String> {
+        self.get(key)?
+            .ok_or_else(|| format!("{}: missing \"{key}\" property", self.path))
+    }
+
+    /// Gets the property named `key` as a number, accepting both integers and floats.
+    fn get_number(&self, key: &str) -> Result<Option<f64>, String> {
+        match self.properties.get(key) {
+            None => Ok(None),
+            Some(Value::Integer(integer)) => Ok(Some(*integer as f64)),
+            Some(Value::Float(float)) => Ok(Some(*float)),
+            Some(_) => Err(format!(
+                "{}: invalid \"{key}\" property, expected a number",
+                self.path
+            )),
+        }
+    }
+
+    /// Gets the property named `key` as a capitalized enum value, such as `Horizontal` for
+    /// `"horizontal"`.
+    fn get_enum(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.get::<String>(key)?.map(|value| {
+            let mut chars = value.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }))
+    }
+
+    /// Prefixes `error` with the path of this node.
+    fn error(&self, error: String) -> String {
+        format!("{}: {error}", self.path)
+    }
+}
+
+impl TreeBuilder {
+    /// Builds `tree` into the currently focused widget, returning a handle to the top-level
+    /// widget of the tree.
+    /// The focused widget is restored once the tree has been built.
+    pub fn build(ui_builder: &UIBuilder, tree: Value) -> Result<WidgetHandle, String> {
+        let parent_identifier = ui_builder
+            .current_user_widget
+            .try_lock()
+            .ok_or("current_user_widget is locked, cannot build the tree!")?
+            .clone()
+            .ok_or("There is no focused widget to build the tree into!")?;
+
+        let result = Self::build_node(ui_builder, &parent_identifier, tree, "tree".to_owned());
+        if ui_builder
+            .user_widgets
+            .read()
+            .contains_key(&parent_identifier)
+        {
+            ui_builder.set_focused_widget(parent_identifier);
+        }
+
+        result
+    }
+
+    /// Builds a single `value` node and all of its children into the widget named
+    /// `parent_identifier`.
+    /// If any child fails to build, the node is removed along with the children built so far, so
+    /// that no part of a failed tree is left in the UI.
+    fn build_node(
+        ui_builder: &UIBuilder,
+        parent_identifier: &str,
+        value: Value,
+        path: String,
+    ) -> Result<WidgetHandle, String> {
+        let properties = rune::from_value::<HashMap<String, Value>>(value)
+            .map_err(|_| format!("{path}: widget nodes must be objects"))?;
+        let node = TreeNode { path, properties };
+        let widget_type: String = node.require("type")?;
+        let identifier: String = node.require("id")?;
+        let Some(type_properties) = Self::get_type_properties(&widget_type) else {
+            return Err(node.error(format!("unknown widget type \"{widget_type}\"")));
+        };
+
+        if let Some(key) = node.properties.keys().find(|key| {
+            !COMMON_PROPERTIES.contains(&key.as_str()) && !type_properties.contains(&key.as_str())
+        }) {
+            return Err(node.error(format!("unknown property \"{key}\" for \"{widget_type}\"")));
+        }
+
+        let widget = Self::create_widget(&node, &widget_type, &identifier)?;
+        Self::apply_common_properties(&node, &widget)?;
+
+        let children: Vec<Value> = node.get("children")?.unwrap_or_default();
+        if !children.is_empty() && !UIBuilder::is_container(&widget) {
+            return Err(node.error(format!("\"{widget_type}\" cannot hold child widgets")));
+        }
+
+        let name: Option<String> = node.get("name")?;
+        ui_builder.set_focused_widget(parent_identifier.to_owned());
+        let handle = ui_builder
+            .add_widget(identifier.to_owned(), widget.clone())
+            .ok_or_else(|| node.error(format!("failed adding \"{identifier}\"")))?;
+
+        if let Some(name) = name {
+            widget.set_widget_name(&name);
+        }
+
+        for (index, child) in children.into_iter().enumerate() {
+            if let Err(error) = Self::build_node(
+                ui_builder,
+                &identifier,
+                child,
+                format!("{}.children[{index}]", node.path),
+            ) {
+                ui_builder.remove_widget(&identifier);
+                return Err(error);
+            }
+        }
+
+        Ok(handle)
+    }
+
+    /// Gets the type-specific properties accepted by `widget_type`, or `None` if the type is
+    /// unknown.
+    fn get_type_properties(widget_type: &str) -> Option<&'static [&'static str]> {
+        Some(match widget_type {
+            "box" => &["orientation", "spacing"],
+            "grid" => &["row_spacing", "column_spacing"],
+            "center_box" | "stack" | "overlay" => &[],
+            "flow_box" => &["max_children_per_line"],
+            "scrolled_window" => &["hpolicy", "vpolicy"],
+            "label" => &["text"],
+            "button" => &["label", "on_click"],
+            "check_button" | "toggle_button" => &["label", "active", "on_toggled"],
+            "switch" => &["active", "on_toggled"],
+            "entry" | "password_entry" | "search_entry" => {
+                &["placeholder", "text", "on_changed", "on_activate"]
+            }
+            "image" => &["file", "icon_name", "pixel_size"],
+            "picture" => &["file"],
+            "dropdown" => &["items", "on_selected"],
+            "progress_bar" => &["orientation", "fraction"],
+            "level_bar" => &["orientation", "min", "max", "value"],
+            "slider" => &["orientation", "min", "max", "step", "value", "on_changed"],
+            _ => return None,
+        })
+    }
+
+    /// Creates the widget described by `node`, connecting its callbacks.
+    fn create_widget(
+        node: &TreeNode,
+        widget_type: &str,
+        identifier: &str,
+    ) -> Result<Widget, String> {
+        let callback_arg: Option<Value> = node.properties.get("callback_arg").cloned();
+        let orientation = node
+            .get_enum("orientation")?
+            .map(|orientation| UIBuilder::get_orientation_from_str(&orientation))
+            .transpose()
+            .map_err(|error| node.error(error))?
+            .unwrap_or(gtk::Orientation::Horizontal);
+
+        let widget: Widget = match widget_type {
+            "box" => gtk::Box::new(orientation, node.get("spacing")?.unwrap_or_default()).into(),
+            "grid" => {
+                let grid = gtk::Grid::new();
+                grid.set_row_spacing(node.get("row_spacing")?.unwrap_or_default());
+                grid.set_column_spacing(node.get("column_spacing")?.unwrap_or_default());
+                grid.into()
+            }
+            "center_box" => gtk::CenterBox::new().into(),
+            "stack" => gtk::Stack::new().into(),
+            "overlay" => gtk::Overlay::new().into(),
+            "flow_box" => {
+                let flow_box = gtk::FlowBox::new();
+                flow_box.set_selection_mode(gtk::SelectionMode::None);
+                if let Some(max_children_per_line) = node.get("max_children_per_line")? {
+                    flow_box.set_max_children_per_line(max_children_per_line);
+                }
+
+                flow_box.into()
+            }
+            "scrolled_window" => {
+                let scrolled_window = gtk::ScrolledWindow::new();
+                let hpolicy = node.get_enum("hpolicy")?.unwrap_or("Automatic".to_owned());
+                let vpolicy = node.get_enum("vpolicy")?.unwrap_or("Automatic".to_owned());
+                scrolled_window.set_policy(
+                    UIBuilder::get_policy_from_str(&hpolicy).map_err(|error| node.error(error))?,
+                    UIBuilder::get_policy_from_str(&vpolicy).map_err(|error| node.error(error))?,
+                );
+                scrolled_window.into()
+            }
+            "label" => gtk::Label::new(node.get::<String>("text")?.as_deref()).into(),
+            "button" => {
+                let button =
+                    gtk::Button::with_label(&node.get::<String>("label")?.unwrap_or_default());
+                if let Some(on_click) = node.get::<Function>("on_click")? {
+                    UIBuilder::connect_state_callback(
+                        identifier,
+                        button.upcast_ref(),
+                        on_click,
+                        callback_arg,
+                    );
+                }
+
+                button.into()
+            }
+            "check_button" | "toggle_button" | "switch" => {
+                let widget: Widget = match widget_type {
+                    "check_button" => gtk::CheckButton::with_label(
+                        &node.get::<String>("label")?.unwrap_or_default(),
+                    )
+                    .into(),
+                    "toggle_button" => gtk::ToggleButton::with_label(
+                        &node.get::<String>("label")?.unwrap_or_default(),
+                    )
+                    .into(),
+                    _ => gtk::Switch::new().into(),
+                };
+
+                if let Some(active) = node.get("active")? {
+                    UIBuilder::set_active(&widget, active);
+                }
+
+                if let Some(on_toggled) = node.get::<Function>("on_toggled")? {
+                    UIBuilder::connect_state_callback(
+                        identifier,
+                        &widget,
+                        on_toggled,
+                        callback_arg,
+                    );
+                }
+
+                widget
+            }
+            "entry" | "password_entry" | "search_entry" => {
+                let placeholder = node.get::<String>("placeholder")?;
+                let widget: Widget = match widget_type {
+                    "entry" => gtk::Entry::builder()
+                        .placeholder_text(placeholder.unwrap_or_default())
+                        .build()
+                        .into(),
+                    "password_entry" => gtk::PasswordEntry::builder()
+                        .placeholder_text(placeholder.unwrap_or_default())
+                        .show_peek_icon(true)
+                        .build()
+                        .into(),
+                    _ => gtk::SearchEntry::builder()
+                        .placeholder_text(placeholder.unwrap_or_default())
+                        .build()
+                        .into(),
+                };
+
+                let editable = widget
+                    .dynamic_cast_ref::<gtk::Editable>()
+                    .expect("[ERROR] Entry widget isn't editable!");
+                if let Some(text) = node.get::<String>("text")? {
+                    editable.set_text(&text);
+                }
+
+                UIBuilder::connect_entry_callbacks(
+                    identifier,
+                    editable,
+                    node.get("on_changed")?,
+                    node.get("on_activate")?,
+                    callback_arg,
+                );
+                widget
+            }
+            "image" => {
+                let image = match (
+                    node.get::<String>("file")?,
+                    node.get::<String>("icon_name")?,
+                ) {
+                    (Some(file), None) => gtk::Image::from_paintable(Some(
+                        &UIBuilder::load_texture(&file).map_err(|error| node.error(error))?,
+                    )),
+                    (None, Some(icon_name)) => {
+                        UIBuilder::check_icon_exists(&icon_name)
+                            .map_err(|error| node.error(error))?;
+                        gtk::Image::from_icon_name(&icon_name)
+                    }
+                    _ => {
+                        return Err(node.error(
+                            "images need exactly one of \"file\" or \"icon_name\"".to_owned(),
+                        ))
+                    }
+                };
+
+                if let Some(pixel_size) = node.get("pixel_size")? {
+                    image.set_pixel_size(pixel_size);
+                }
+
+                image.into()
+            }
+            "picture" => gtk::Picture::for_paintable(
+                &UIBuilder::load_texture(&node.require::<String>("file")?)
+                    .map_err(|error| node.error(error))?,
+            )
+            .into(),
+            "dropdown" => {
+                let items: Vec<String> = node.get("items")?.unwrap_or_default();
+                let items: Vec<&str> = items.iter().map(String::as_str).collect();
+                let dropdown = gtk::DropDown::from_strings(&items);
+                if let Some(on_selected) = node.get::<Function>("on_selected")? {
+                    UIBuilder::connect_state_callback(
+                        identifier,
+                        dropdown.upcast_ref(),
+                        on_selected,
+                        callback_arg,
+                    );
+                }
+
+                dropdown.into()
+            }
+            "progress_bar" => {
+                let progress_bar = gtk::ProgressBar::new();
+                progress_bar.set_orientation(orientation);
+                progress_bar.set_fraction(node.get_number("fraction")?.unwrap_or_default());
+                progress_bar.into()
+            }
+            "level_bar" => {
+                let level_bar = gtk::LevelBar::for_interval(
+                    node.get_number("min")?.unwrap_or(0.0),
+                    node.get_number("max")?.unwrap_or(1.0),
+                );
+                level_bar.set_orientation(orientation);
+                level_bar.set_value(node.get_number("value")?.unwrap_or_default());
+                level_bar.into()
+            }
+            "slider" => {
+                let slider = gtk::Scale::with_range(
+                    orientation,
+                    node.get_number("min")?.unwrap_or(0.0),
+                    node.get_number("max")?.unwrap_or(1.0),
+                    node.get_number("step")?.unwrap_or(0.1),
+                );
+                if let Some(value) = node.get_number("value")? {
+                    slider.set_value(value);
+                }
+
+                if let Some(on_changed) = node.get::<Function>("on_changed")? {
+                    UIBuilder::connect_state_callback(
+                        identifier,
+                        slider.upcast_ref(),
+                        on_changed,
+                        callback_arg,
+                    );
+                }
+
+                slider.into()
+            }
+            _ => unreachable!("[FATAL] Widget type was validated by get_type_properties!"),
+        };
+
+        Ok(widget)
+    }
+
+    /// Applies the properties from `COMMON_PROPERTIES` to `widget`.
+    /// The `name` property is applied once the widget has been added, as adding it names the
+    /// widget after its identifier.
+    fn apply_common_properties(node: &TreeNode, widget: &Widget) -> Result<(), String> {
+        if let Some(tooltip) = node.get::<String>("tooltip")? {
+            widget.set_tooltip_text(Some(&tooltip));
+        }
+
+        if let Some(visible) = node.get("visible")? {
+            widget.set_visible(visible);
+        }
+
+        if let Some(halign) = node.get_enum("halign")? {
+            widget.set_halign(
+                UIBuilder::get_align_from_str(&halign).map_err(|error| node.error(error))?,
+            );
+        }
+
+        if let Some(valign) = node.get_enum("valign")? {
+            widget.set_valign(
+                UIBuilder::get_align_from_str(&valign).map_err(|error| node.error(error))?,
+            );
+        }
+
+        if let Some(hexpand) = node.get("hexpand")? {
+            widget.set_hexpand(hexpand);
+        }
+
+        if let Some(vexpand) = node.get("vexpand")? {
+            widget.set_vexpand(vexpand);
+        }
+
+        if let Some(width) = node.get("width")? {
+            widget.set_width_request(width);
+        }
+
+        if let Some(height) = node.get("height")? {
+            widget.set_height_request(height);
+        }
+
+        if let Some(opacity) = node.get_number("opacity")? {
+            widget.set_opacity(opacity);
+        }
+
+        if let Some(margin_start) = node.get("margin_start")? {
+            widget.set_margin_start(margin_start);
+        }
+
+        if let Some(margin_end) = node.get("margin_end")? {
+            widget.set_margin_end(margin_end);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use super::UIBuilder;
+use gtk::{prelude::*, Widget};
+use rune::{Any, Module};
+
+/// Handle to a user widget, returned to Rune by the `add_*` functions.
+/// Unlike the string-based functions, the handle methods operate on their own widget rather than
+/// the currently focused one.
+#[derive(Any, Clone, Debug)]
+#[rune(item = ::GTK, name = Widget)]
+pub struct WidgetHandle {
+    /// Identifier of the user widget.
+    identifier: String,
+}
+
+impl WidgetHandle {
+    /// Creates a new handle for the user widget named `identifier`.
+    pub fn new(identifier: String) -> Self {
+        Self { identifier }
+    }
+
+    /// Registers the `Widget` type and its methods into the GTK module.
+    pub fn register(gtk_module: &mut Module, ui_builder: &'static UIBuilder) {
+        gtk_module
+            .ty::<Self>()
+            .expect("[ERROR] Failed registering the Widget type!");
+
+        gtk_module
+            .associated_function("identifier", |handle: &Self| handle.identifier.to_owned())
+            .unwrap();
+
+        gtk_module
+            .associated_function("exists", move |handle: &Self| {
+                ui_builder
+                    .user_widgets
+                    .read()
+                    .contains_key(&handle.identifier)
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("focus", move |handle: &Self| {
+                ui_builder.set_focused_widget(handle.identifier.to_owned())
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("set_visible", move |handle: &Self, visible: bool| {
+                handle.with_widget(ui_builder, |widget| widget.set_visible(visible))
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("is_visible", move |handle: &Self| {
+                handle.with_widget(ui_builder, |widget| widget.is_visible())
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("set_opacity", move |handle: &Self, opacity: f64| {
+                handle.with_widget(ui_builder, |widget| widget.set_opacity(opacity))
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("set_hexpand", move |handle: &Self, expand: bool| {
+                handle.with_widget(ui_builder, |widget| widget.set_hexpand(expand))
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("set_vexpand", move |handle: &Self, expand: bool| {
+                handle.with_widget(ui_builder, |widget| widget.set_vexpand(expand))
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("set_halign", move |handle: &Self, align: String| {
+                let align = UIBuilder::get_align_from_str(&align)?;
+                Ok::<_, String>(handle.with_widget(ui_builder, |widget| widget.set_halign(align)))
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("set_valign", move |handle: &Self, align: String| {
+                let align = UIBuilder::get_align_from_str(&align)?;
+                Ok::<_, String>(handle.with_widget(ui_builder, |widget| widget.set_valign(align)))
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function(
+                "set_size_request",
+                move |handle: &Self, width: i32, height: i32| {
+                    handle.with_widget(ui_builder, |widget| widget.set_size_request(width, height))
+                },
+            )
+            .unwrap();
+
+        gtk_module
+            .associated_function(
+                "set_tooltip_text",
+                move |handle: &Self, text: Option<String>| {
+                    handle.with_widget(ui_builder, |widget| {
+                        widget.set_tooltip_text(text.as_deref())
+                    })
+                },
+            )
+            .unwrap();
+
+        gtk_module
+            .associated_function("set_text", move |handle: &Self, text: String| {
+                handle
+                    .with_widget(ui_builder, |widget| Self::set_widget_text(widget, &text))
+                    .unwrap_or_default()
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("get_text", move |handle: &Self| {
+                handle
+                    .with_widget(ui_builder, Self::get_widget_text)
+                    .flatten()
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("set_active", move |handle: &Self, active: bool| {
+                handle.with_widget(ui_builder, |widget| UIBuilder::set_active(widget, active))
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("get_active", move |handle: &Self| {
+                handle
+                    .with_widget(ui_builder, UIBuilder::get_active)
+                    .flatten()
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("children", move |handle: &Self| {
+                ui_builder
+                    .get_child_identifiers(&handle.identifier)
+                    .into_iter()
+                    .map(Self::new)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("parent", move |handle: &Self| {
+                ui_builder
+                    .get_parent_identifier(&handle.identifier)
+                    .map(Self::new)
+            })
+            .unwrap();
+
+        gtk_module
+            .associated_function("remove", move |handle: &Self| {
+                ui_builder.remove_widget(&handle.identifier)
+            })
+            .unwrap();
+    }
+
+    /// Calls `function` with the widget this handle points at, returning `None` if the widget
+    /// doesn't exist anymore.
+    fn with_widget<R>(
+        &self,
+        ui_builder: &UIBuilder,
+        function: impl FnOnce(&Widget) -> R,
+    ) -> Option<R> {
+        ui_builder
+            .get_user_widget(&self.identifier)
+            .map(|widget| function(&widget))
+    }
+
+    /// Sets the text of a label, button or entry, returning `true` if the widget has any text.
+    fn set_widget_text(widget: &Widget, text: &str) -> bool {
+        if let Some(label) = widget.downcast_ref::<gtk::Label>() {
+            label.set_text(text);
+        } else if let Some(button) = widget.downcast_ref::<gtk::Button>() {
+            button.set_label(text);
+        } else if let Some(check_button) = widget.downcast_ref::<gtk::CheckButton>() {
+            check_button.set_label(Some(text));
+        } else if let Some(editable) = widget.dynamic_cast_ref::<gtk::Editable>() {
+            editable.set_text(text);
+        } else {
+            eprintln!(
+                "[ERROR] \"{}\" doesn't have any text!",
+                widget.type_().name()
+            );
+            return false;
+        }
+
+        true
+    }
+
+    /// Gets the text of a label, button or entry.
+    fn get_widget_text(widget: &Widget) -> Option<String> {
+        if let Some(label) = widget.downcast_ref::<gtk::Label>() {
+            return Some(label.text().to_string());
+        }
+
+        if let Some(button) = widget.downcast_ref::<gtk::Button>() {
+            return button.label().map(|label| label.to_string());
+        }
+
+        if let Some(check_button) = widget.downcast_ref::<gtk::CheckButton>() {
+            return check_button.label().map(|label| label.to_string());
+        }
+
+        if let Some(editable) = widget.dynamic_cast_ref::<gtk::Editable>() {
+            return Some(editable.text().to_string());
+        }
+
+        eprintln!(
+            "[ERROR] \"{}\" doesn't have any text!",
+            widget.type_().name()
+        );
+        None
+    }
+}
@@ -0,0 +1,398 @@
+use gdk4_x11::{X11Display, X11Surface};
+use gtk::{
+    gdk::{Monitor, Rectangle},
+    prelude::*,
+    ApplicationWindow,
+};
+use gtk4_layer_shell::{KeyboardMode, Layer};
+use std::ffi::{c_int, c_long, c_uchar};
+use x11::xlib;
+
+/// Layer-shell settings of a window on X11, where they're emulated through a dock-type window
+/// with strut properties.
+/// Edges are indexed in the `Left`, `Right`, `Top` and `Bottom` order.
+pub struct X11Dock {
+    /// Edges the window is anchored to.
+    pub anchors: [bool; 4],
+
+    /// Margins of the window from each edge.
+    pub margins: [i32; 4],
+
+    /// Space reserved for the window along its anchored edge.
+    pub exclusive_zone: i32,
+
+    /// Whether the space reserved for the window follows its size.
+    pub auto_exclusive_zone: bool,
+
+    /// Monitor the window is placed on, or `None` to use the one the window is on.
+    pub monitor: Option<Monitor>,
+
+    /// Requested layer, only stored as X11 window managers keep docks above other windows.
+    pub layer: Layer,
+
+    /// Requested keyboard mode, only stored as X11 has no equivalent.
+    pub keyboard_mode: KeyboardMode,
+}
+
+impl Default for X11Dock {
+    fn default() -> Self {
+        Self {
+            anchors: [false; 4],
+            margins: [0; 4],
+            exclusive_zone: 0,
+            auto_exclusive_zone: false,
+            monitor: None,
+            layer: Layer::Top,
+            keyboard_mode: KeyboardMode::None,
+        }
+    }
+}
+
+impl X11Dock {
+    /// Checks if the default display is an X11 display.
+    pub fn is_supported() -> bool {
+        gtk::gdk::Display::default().is_some_and(|display| display.is::<X11Display>())
+    }
+
+    /// Applies the settings to `window`, turning it into a dock with strut properties and moving
+    /// it to its anchored position.
+    /// Does nothing if `window` hasn't been realized yet.
+    pub fn apply(&self, window: &ApplicationWindow) {
+        let Some(surface) = window
+            .surface()
+            .and_then(|surface| surface.downcast::<X11Surface>().ok())
+        else {
+            return;
+        };
+
+        let Ok(display) = surface.display().downcast::<X11Display>() else {
+            return;
+        };
+
+        let Some(monitor) = self
+            .monitor
+            .to_owned()
+            .or_else(|| display.monitor_at_surface(&surface))
+        else {
+            eprintln!("[ERROR] Couldn't find the monitor of the dock window!");
+            return;
+        };
+
+        // Anchoring to opposite edges stretches the window between them, like the layer-shell.
+        let geometry = monitor.geometry();
+        let [left, right, top, bottom] = self.anchors;
+        let [margin_left, margin_right, margin_top, margin_bottom] = self.margins;
+        if left && right {
+            window.set_default_width(geometry.width() - margin_left - margin_right);
+        }
+
+        if top && bottom {
+            window.set_default_height(geometry.height() - margin_top - margin_bottom);
+        }
+
+        let bounds = Rectangle::new(
+            Self::get_position(
+                (left, right),
+                (geometry.x(), geometry.width()),
+                window.width(),
+                (margin_left, margin_right),
+            ),
+            Self::get_position(
+                (top, bottom),
+                (geometry.y(), geometry.height()),
+                window.height(),
+                (margin_top, margin_bottom),
+            ),
+            window.width(),
+            window.height(),
+        );
+
+        surface.set_skip_taskbar_hint(true);
+        surface.set_skip_pager_hint(true);
+
+        let scale = surface.scale_factor();
+        let dock_type =
+            gdk4_x11::x11_get_xatom_by_name_for_display(&display, "_NET_WM_WINDOW_TYPE_DOCK");
+        // Safety: The X display is owned by GDK, and outlives this call.
+        let screen_size = unsafe {
+            let xdisplay = display.xdisplay();
+            let screen = xlib::XDefaultScreen(xdisplay);
+            (
+                xlib::XDisplayWidth(xdisplay, screen),
+                xlib::XDisplayHeight(xdisplay, screen),
+            )
+        };
+        let strut = self.get_strut_partial(screen_size, &geometry, &bounds, scale);
+
+        // Safety: The X display and window are owned by GDK, and stay alive while the window is
+        // realized.
+        unsafe {
+            Self::set_property(
+                &surface,
+                &display,
+                "_NET_WM_WINDOW_TYPE",
+                xlib::XA_ATOM,
+                &[dock_type as c_long],
+            );
+            Self::set_property(
+                &surface,
+                &display,
+                "_NET_WM_STRUT",
+                xlib::XA_CARDINAL,
+                &strut[..4],
+            );
+            Self::set_property(
+                &surface,
+                &display,
+                "_NET_WM_STRUT_PARTIAL",
+                xlib::XA_CARDINAL,
+                &strut,
+            );
+            xlib::XMoveWindow(
+                display.xdisplay(),
+                surface.xid(),
+                bounds.x() * scale,
+                bounds.y() * scale,
+            );
+        }
+    }
+
+    /// Gets the position of the window along an axis, from whether it's anchored to the start and
+    /// end edges of the axis, the origin and length of the monitor along the axis, the size of
+    /// the window and its margins.
+    fn get_position(
+        (start, end): (bool, bool),
+        (origin, length): (i32, i32),
+        size: i32,
+        (margin_start, margin_end): (i32, i32),
+    ) -> i32 {
+        match (start, end) {
+            (true, _) => origin + margin_start,
+            (false, true) => origin + length - size - margin_end,
+            (false, false) => origin + (length - size) / 2,
+        }
+    }
+
+    /// Gets the index of the edge the exclusive zone is reserved along, which is the only edge of
+    /// an axis the window is anchored to.
+    fn get_exclusive_edge(&self) -> Option<usize> {
+        let [left, right, top, bottom] = self.anchors;
+        if top != bottom {
+            return Some(if top { 2 } else { 3 });
+        }
+
+        if left != right {
+            return Some(if left { 0 } else { 1 });
+        }
+
+        None
+    }
+
+    /// Gets the `_NET_WM_STRUT_PARTIAL` values for the window at `bounds` on the monitor at
+    /// `geometry`, which are relative to the edges of the whole X screen of `screen_size`.
+    fn get_strut_partial(
+        &self,
+        (screen_width, screen_height): (i32, i32),
+        geometry: &Rectangle,
+        bounds: &Rectangle,
+        scale: i32,
+    ) -> [c_long; 12] {
+        let mut strut = [0; 12];
+        let Some(edge) = self.get_exclusive_edge() else {
+            return strut;
+        };
+
+        let exclusive_zone = match (self.auto_exclusive_zone, edge) {
+            (true, 0 | 1) => bounds.width(),
+            (true, _) => bounds.height(),
+            (false, _) => self.exclusive_zone,
+        };
+
+        if exclusive_zone <= 0 {
+            return strut;
+        }
+
+        let zone_size = (exclusive_zone + self.margins[edge]) * scale;
+        let (x, y) = (bounds.x() * scale, bounds.y() * scale);
+        let (width, height) = (bounds.width() * scale, bounds.height() * scale);
+        let (reserved, range_start, range_length) = match edge {
+            0 => (geometry.x() * scale + zone_size, y, height),
+            1 => (
+                screen_width - (geometry.x() + geometry.width()) * scale + zone_size,
+                y,
+                height,
+            ),
+            2 => (geometry.y() * scale + zone_size, x, width),
+            _ => (
+                screen_height - (geometry.y() + geometry.height()) * scale + zone_size,
+                x,
+                width,
+            ),
+        };
+
+        strut[edge] = reserved as c_long;
+        strut[4 + edge * 2] = range_start as c_long;
+        strut[5 + edge * 2] = (range_start + range_length - 1) as c_long;
+        strut
+    }
+
+    /// Sets the 32-bit `property` of `surface` to `data`.
+    unsafe fn set_property(
+        surface: &X11Surface,
+        display: &X11Display,
+        property: &str,
+        kind: xlib::Atom,
+        data: &[c_long],
+    ) {
+        xlib::XChangeProperty(
+            display.xdisplay(),
+            surface.xid(),
+            gdk4_x11::x11_get_xatom_by_name_for_display(display, property),
+            kind,
+            32,
+            xlib::PropModeReplace,
+            data.as_ptr() as *const c_uchar,
+            data.len() as c_int,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates dock settings anchored to the `[left, right, top, bottom]` edges.
+    fn dock_anchored_to(anchors: [bool; 4]) -> X11Dock {
+        X11Dock {
+            anchors,
+            auto_exclusive_zone: true,
+            ..X11Dock::default()
+        }
+    }
+
+    #[test]
+    fn position_follows_anchors_and_margins() {
+        assert_eq!(
+            X11Dock::get_position((true, false), (100, 1000), 200, (10, 20)),
+            110
+        );
+        assert_eq!(
+            X11Dock::get_position((false, true), (100, 1000), 200, (10, 20)),
+            880
+        );
+        assert_eq!(
+            X11Dock::get_position((false, false), (100, 1000), 200, (10, 20)),
+            500
+        );
+        assert_eq!(
+            X11Dock::get_position((true, true), (100, 1000), 200, (10, 20)),
+            110
+        );
+    }
+
+    #[test]
+    fn exclusive_edge_is_the_only_anchored_edge_of_an_axis() {
+        assert_eq!(
+            dock_anchored_to([true, true, true, false]).get_exclusive_edge(),
+            Some(2)
+        );
+        assert_eq!(
+            dock_anchored_to([true, true, false, true]).get_exclusive_edge(),
+            Some(3)
+        );
+        assert_eq!(
+            dock_anchored_to([true, false, true, true]).get_exclusive_edge(),
+            Some(0)
+        );
+        assert_eq!(
+            dock_anchored_to([false, true, true, true]).get_exclusive_edge(),
+            Some(1)
+        );
+        assert_eq!(dock_anchored_to([true; 4]).get_exclusive_edge(), None);
+        assert_eq!(dock_anchored_to([false; 4]).get_exclusive_edge(), None);
+    }
+
+    #[test]
+    fn strut_of_a_top_bar_uses_its_height() {
+        let dock = dock_anchored_to([true, true, true, false]);
+        let strut = dock.get_strut_partial(
+            (1920, 1080),
+            &Rectangle::new(0, 0, 1920, 1080),
+            &Rectangle::new(0, 0, 1920, 30),
+            1,
+        );
+
+        assert_eq!(strut, [0, 0, 30, 0, 0, 0, 0, 0, 0, 1919, 0, 0]);
+    }
+
+    #[test]
+    fn strut_of_a_left_bar_uses_its_width() {
+        let dock = dock_anchored_to([true, false, true, true]);
+        let strut = dock.get_strut_partial(
+            (1920, 1080),
+            &Rectangle::new(0, 0, 1920, 1080),
+            &Rectangle::new(0, 0, 48, 1080),
+            1,
+        );
+
+        assert_eq!(strut, [48, 0, 0, 0, 0, 1079, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn strut_is_relative_to_the_whole_screen() {
+        let dock = dock_anchored_to([true, true, false, true]);
+        let strut = dock.get_strut_partial(
+            (3840, 1440),
+            &Rectangle::new(1920, 0, 1920, 1080),
+            &Rectangle::new(1920, 1050, 1920, 30),
+            1,
+        );
+
+        assert_eq!(strut, [0, 0, 0, 390, 0, 0, 0, 0, 0, 0, 1920, 3839]);
+    }
+
+    #[test]
+    fn strut_of_a_right_bar_on_the_left_monitor_covers_the_right_monitor() {
+        let dock = dock_anchored_to([false, true, true, true]);
+        let strut = dock.get_strut_partial(
+            (3840, 1080),
+            &Rectangle::new(0, 0, 1920, 1080),
+            &Rectangle::new(1872, 0, 48, 1080),
+            1,
+        );
+
+        assert_eq!(strut, [0, 1968, 0, 0, 0, 0, 0, 1079, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fixed_exclusive_zone_includes_the_margin_and_scale() {
+        let dock = X11Dock {
+            anchors: [true, true, true, false],
+            margins: [0, 0, 5, 0],
+            exclusive_zone: 40,
+            ..X11Dock::default()
+        };
+        let strut = dock.get_strut_partial(
+            (1920, 1080),
+            &Rectangle::new(0, 0, 960, 540),
+            &Rectangle::new(0, 5, 960, 30),
+            2,
+        );
+
+        assert_eq!(strut, [0, 0, 90, 0, 0, 0, 0, 0, 0, 1919, 0, 0]);
+    }
+
+    #[test]
+    fn strut_is_empty_without_an_exclusive_zone() {
+        let mut dock = dock_anchored_to([true, true, true, false]);
+        dock.auto_exclusive_zone = false;
+        let strut = dock.get_strut_partial(
+            (1920, 1080),
+            &Rectangle::new(0, 0, 1920, 1080),
+            &Rectangle::new(0, 0, 1920, 30),
+            1,
+        );
+
+        assert_eq!(strut, [0; 12]);
+    }
+}
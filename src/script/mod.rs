@@ -1,19 +1,23 @@
 use crate::{builder::stdext_crate::STDExtCrate, utils::SystemUtils};
+use parking_lot::RwLock;
 use rune::{
-    runtime::{Function, SyncFunction, Value},
+    runtime::{Function, RuntimeContext, SyncFunction, Value},
     termcolor::*,
     *,
 };
 use std::{
     cell::{OnceCell, RefCell},
-    sync::{Arc, OnceLock},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 /// Data collected from the `main` function return data.
 #[derive(Any)]
 struct MainReturnData {
     /// Single argument to be passed into every event function, such as a structure instance.
-    pub event_arg: &'static SafeValue,
+    pub event_arg: SafeValue,
 
     /// `on_enter_widget` Rune function.
     pub on_enter_widget_rfn: SyncFunction,
@@ -25,14 +29,23 @@ struct MainReturnData {
 /// Script Engine active for this particular instance.
 #[derive(Default)]
 pub struct ScriptEngine {
-    /// The allocated Rune Virtual Machine.
-    rune_vm: OnceCell<RefCell<Vm>>,
+    /// The allocated Rune Virtual Machine, replaced every time the script is recompiled.
+    rune_vm: RefCell<Option<Vm>>,
+
+    /// Runtime context and unit of the compiled script, used for looking up functions by name
+    /// without borrowing `rune_vm`.
+    compiled_runtime: RefCell<Option<(Arc<RuntimeContext>, Arc<Unit>)>>,
 
     /// Rune UI module to be installed.
     ui_modules: OnceCell<Vec<Module>>,
 
-    /// Data collected from the `main` function return data.
-    main_return_data: Arc<OnceLock<MainReturnData>>,
+    /// Data collected from the `main` function return data, reset every time the script is
+    /// recompiled.
+    main_return_data: Arc<RwLock<Option<Arc<MainReturnData>>>>,
+
+    /// Generation of the compiled script, increased every time the script is recompiled so that
+    /// background loops started by older scripts stop themselves.
+    script_generation: Arc<AtomicUsize>,
 
     /// System Utils instance.
     system_utils: Arc<SystemUtils>,
@@ -50,6 +63,8 @@ unsafe impl Sync for ScriptEngine {}
 impl ScriptEngine {
     /// Builds a new virtual machine from the source `input` and then calls the `main` function on the
     /// source.
+    /// If a script has already been compiled, it's only replaced once `input` compiles
+    /// successfully, stopping everything the previous script has started.
     pub fn run_from_input(self: Arc<Self>, input: &str) -> rune::support::Result<()> {
         let mut context = Context::with_default_modules()?;
         let mut module = Module::new();
@@ -60,18 +75,24 @@ impl ScriptEngine {
             .function(
                 "init_runtime_config",
                 move |event_arg, on_enter_exit_widget_rfn: (Function, Function)| {
-                    main_return_data_clone.get_or_init(|| MainReturnData {
-                        event_arg: Box::leak(Box::new(SafeValue(event_arg))),
-                        on_enter_widget_rfn: on_enter_exit_widget_rfn
-                            .0
-                            .into_sync()
-                            .into_result()
-                            .expect("[ERROR] Failed turning on_enter_widget into a SyncFunction!"),
-                        on_exit_widget_rfn: on_enter_exit_widget_rfn
-                            .1
-                            .into_sync()
-                            .into_result()
-                            .expect("[ERROR] Failed turning on_exit_widget into a SyncFunction!"),
+                    main_return_data_clone.write().get_or_insert_with(|| {
+                        Arc::new(MainReturnData {
+                            event_arg: SafeValue(event_arg),
+                            on_enter_widget_rfn: on_enter_exit_widget_rfn
+                                .0
+                                .into_sync()
+                                .into_result()
+                                .expect(
+                                    "[ERROR] Failed turning on_enter_widget into a SyncFunction!",
+                                ),
+                            on_exit_widget_rfn: on_enter_exit_widget_rfn
+                                .1
+                                .into_sync()
+                                .into_result()
+                                .expect(
+                                    "[ERROR] Failed turning on_exit_widget into a SyncFunction!",
+                                ),
+                        })
                     });
                 },
             )
@@ -101,21 +122,34 @@ impl ScriptEngine {
             diagnostics.emit(&mut StandardStream::stderr(ColorChoice::Auto), &sources)?;
         }
 
-        let vm = Vm::new(Arc::new(context.runtime()?), Arc::new(result?));
-        self.rune_vm.get_or_init(|| RefCell::new(vm));
+        let runtime = Arc::new(context.runtime()?);
+        let unit = Arc::new(result?);
+
+        // The script compiled successfully, stop the previous one before swapping it out.
+        self.reset_runtime_state();
+        *self.compiled_runtime.borrow_mut() = Some((Arc::clone(&runtime), Arc::clone(&unit)));
+        *self.rune_vm.borrow_mut() = Some(Vm::new(runtime, unit));
         Ok(())
     }
 
+    /// Stops the background loops and listening commands started by the current script, and
+    /// forgets its runtime config.
+    fn reset_runtime_state(&self) {
+        self.script_generation.fetch_add(1, Ordering::SeqCst);
+        self.system_utils.stop_listening_commands();
+        *self.main_return_data.write() = None;
+    }
+
     /// Calls the `main` function on the VM.
     pub fn call_main(&self) -> rune::support::Result<()> {
         let mut vm = self
             .rune_vm
-            .get()
-            .expect("[ERROR] No VM has been stored!")
             .try_borrow_mut()
             .expect("[ERROR] VM is already being borrowed, cannot borrow as mutable!");
 
-        vm.call(["main"], ())?;
+        vm.as_mut()
+            .expect("[ERROR] No VM has been stored!")
+            .call(["main"], ())?;
         Ok(())
     }
 
@@ -123,12 +157,14 @@ impl ScriptEngine {
     pub fn call_on_ui_pre_init(&self) -> rune::support::Result<()> {
         let vm = self
             .rune_vm
-            .get()
-            .expect("[ERROR] No VM has been stored!")
             .try_borrow()
             .expect("[ERROR] VM is already being borrowed, cannot borrow as of now!");
 
-        let Ok(ui_pre_init) = vm.lookup_function(["on_ui_pre_init"]) else {
+        let Ok(ui_pre_init) = vm
+            .as_ref()
+            .expect("[ERROR] No VM has been stored!")
+            .lookup_function(["on_ui_pre_init"])
+        else {
             println!("[INFO] No on_ui_pre_init function, skipping.");
             return Ok(());
         };
@@ -143,7 +179,7 @@ impl ScriptEngine {
 
     /// Calls the `on_enter_widget` or `on_exit_widget` function on the VM if present.
     pub fn call_enter_exit(&self, identifier: &str, entered: bool) -> bool {
-        let Some(main_return_data) = self.main_return_data.get() else {
+        let Some(main_return_data) = self.main_return_data.read().clone() else {
             eprintln!(
                 "[WARN] No event functions were stored at startup, skipping advanced events!"
             );
@@ -165,6 +201,44 @@ impl ScriptEngine {
             .is_ok()
     }
 
+    /// Checks if the compiled script defines a function named `name`, such as `on_click` or
+    /// `Bar::on_click`.
+    pub fn has_function(&self, name: &str) -> bool {
+        let Some((runtime, unit)) = self.compiled_runtime.borrow().clone() else {
+            return false;
+        };
+
+        Vm::new(runtime, unit)
+            .lookup_function(name.split("::").collect::<Vec<_>>().as_slice())
+            .is_ok()
+    }
+
+    /// Calls the function named `name`, such as `on_click` or `Bar::on_click`, with the event
+    /// argument and the identifier of the object that triggered it.
+    /// The event argument is `()` if no runtime config has been created.
+    pub fn call_function_by_name(&self, name: &str, identifier: &str) -> Result<Value, String> {
+        let Some((runtime, unit)) = self.compiled_runtime.borrow().clone() else {
+            return Err("No script has been compiled!".to_owned());
+        };
+
+        let function = Vm::new(runtime, unit)
+            .lookup_function(name.split("::").collect::<Vec<_>>().as_slice())
+            .map_err(|_| format!("No function named \"{name}\" has been defined!"))?;
+
+        let event_arg = self
+            .main_return_data
+            .read()
+            .as_ref()
+            .map_or(Value::EmptyTuple, |main_return_data| {
+                main_return_data.event_arg.0.clone()
+            });
+
+        function
+            .call::<_, Value>((event_arg, identifier))
+            .into_result()
+            .map_err(|error| format!("Failed calling \"{name}\", error: {error}"))
+    }
+
     /// Starts a new background loop.
     pub fn start_background_loop(
         &self,
@@ -172,16 +246,25 @@ impl ScriptEngine {
         loop_function: SyncFunction,
         time: u64,
     ) {
-        let Some(main_return_data) = self.main_return_data.get() else {
+        let Some(main_return_data) = self.main_return_data.read().clone() else {
             eprintln!(
                 "[ERROR] No runtime config has been created, background loops cannot be started!"
             );
             return;
         };
 
-        let event_arg = main_return_data.event_arg;
+        let generation = self.script_generation.load(Ordering::SeqCst);
+        let script_generation = Arc::clone(&self.script_generation);
         gtk::glib::timeout_add(std::time::Duration::from_millis(time), move || {
-            if let Err(error) = loop_function.call::<_, ()>((&event_arg.0,)).into_result() {
+            // The script has been reloaded since this loop was started.
+            if script_generation.load(Ordering::SeqCst) != generation {
+                return gtk::glib::ControlFlow::Break;
+            }
+
+            if let Err(error) = loop_function
+                .call::<_, ()>((&main_return_data.event_arg.0,))
+                .into_result()
+            {
                 eprintln!(
                     "[ERROR] Background loop \"{identifier}\" has panicked and been immediately stopped!"
                 );
@@ -1,9 +1,12 @@
 use ahash::AHashMap;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::{
     io::{BufRead, BufReader},
-    process::{Command, Stdio},
-    sync::Arc,
+    process::{Child, Command, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 /// System-related utilities.
@@ -14,6 +17,13 @@ pub struct SystemUtils {
     /// Each value is wrapped inside RwLock<String> so that it's easier to use it in multiple
     /// background loops, without blocking the entire HashMap when writing to a single key.
     listening_command_outputs: Arc<RwLock<AHashMap<String, RwLock<String>>>>,
+
+    /// The processes of all listening commands, so that they can be stopped.
+    listening_command_processes: Arc<Mutex<AHashMap<String, Child>>>,
+
+    /// Incremented whenever the listening commands are stopped, so that the threads reading
+    /// their output know when to stop.
+    listening_command_generation: Arc<AtomicUsize>,
 }
 
 impl SystemUtils {
@@ -52,43 +62,46 @@ impl SystemUtils {
     }
 
     /// Starts a new listening command instance.
+    /// The process is registered before its output is read in a background thread, which stops
+    /// writing the output once the listening commands have been stopped.
     pub fn start_listening_command(&self, identifier: String, cmd: String) {
         let listening_command_outputs = self.get_listening_command_outputs();
-        std::thread::spawn(move || {
-            if listening_command_outputs
-                .try_read()
-                .expect("[ERROR] listening_command_outputs is locked!")
-                .contains_key(&identifier)
-            {
-                eprintln!("[ERROR] There is already a listening command named \"{identifier}\"!");
-                return;
-            }
+        let listening_command_processes = Arc::clone(&self.listening_command_processes);
+        let listening_command_generation = Arc::clone(&self.listening_command_generation);
+        let mut outputs = listening_command_outputs.write();
+        if outputs.contains_key(&identifier) {
+            eprintln!("[ERROR] There is already a listening command named \"{identifier}\"!");
+            return;
+        }
 
-            listening_command_outputs
-                .try_write()
-                .expect("[ERROR] listening_command_outputs is locked!")
-                .insert(identifier.to_owned(), RwLock::default());
+        let child = Command::new("sh")
+            .args(["-c", &cmd])
+            .stdout(Stdio::piped())
+            .spawn();
+        if child.is_err() {
+            eprintln!(
+                "[ERROR] Failed spawning \"sh -c {cmd}\", error: {}",
+                child.unwrap_err()
+            );
+            return;
+        }
 
-            let child = Command::new("sh")
-                .args(["-c", &cmd])
-                .stdout(Stdio::piped())
-                .spawn();
-            if child.is_err() {
-                eprintln!(
-                    "[ERROR] Failed spawning \"sh -c {cmd}\", error: {}",
-                    child.unwrap_err()
-                );
-                return;
-            }
+        let mut child =
+            child.expect("[FATAL] Safety if-condition removed for child process spawning?");
 
-            let mut child =
-                child.expect("[FATAL] Safety if-condition removed for child process spawning?");
+        let Some(out) = child.stdout.take() else {
+            eprintln!("[ERROR] Child process has no stdout to aquire!");
+            return;
+        };
 
-            let Some(out) = child.stdout.take() else {
-                eprintln!("[ERROR] Child process has no stdout to aquire!");
-                return;
-            };
+        let generation = listening_command_generation.load(Ordering::SeqCst);
+        outputs.insert(identifier.to_owned(), RwLock::default());
+        listening_command_processes
+            .lock()
+            .insert(identifier.to_owned(), child);
+        drop(outputs); // Release lock.
 
+        std::thread::spawn(move || {
             let reader = BufReader::new(out);
             for line in reader.lines() {
                 let Some(reader) = listening_command_outputs.try_read() else {
@@ -96,8 +109,13 @@ impl SystemUtils {
                     continue;
                 };
 
+                // The listening command has been stopped, and the identifier may now belong to a
+                // newer one.
+                if listening_command_generation.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+
                 let Some(command) = reader.get(&identifier) else {
-                    eprintln!("[ERROR] There is no listening command named \"{identifier}\"!");
                     return;
                 };
 
@@ -109,12 +127,37 @@ impl SystemUtils {
                 *writer = line.expect("[ERROR] Corrupt UTF-8 String output from process!");
             }
 
-            if let Err(error) = child.wait() {
+            // Only wait on the process if it hasn't been stopped in the meantime.
+            let child = {
+                let mut processes = listening_command_processes.lock();
+                if listening_command_generation.load(Ordering::SeqCst) == generation {
+                    processes.remove(&identifier)
+                } else {
+                    None
+                }
+            };
+
+            if let Some(Err(error)) = child.map(|mut child| child.wait()) {
                 eprintln!("[ERROR] Child process exited, error: {error}");
             }
         });
     }
 
+    /// Stops all listening commands, killing their processes and discarding their outputs.
+    pub fn stop_listening_commands(&self) {
+        self.listening_command_generation
+            .fetch_add(1, Ordering::SeqCst);
+        for (identifier, mut child) in self.listening_command_processes.lock().drain() {
+            if let Err(error) = child.kill().and_then(|_| child.wait()) {
+                eprintln!(
+                    "[ERROR] Failed stopping listening command \"{identifier}\", error: {error}"
+                );
+            }
+        }
+
+        self.listening_command_outputs.write().clear();
+    }
+
     /// All the listening command outputs.
     /// Each key is the unique identifier, whereas the value is the last-read line.
     pub fn get_listening_command_outputs(&self) -> Arc<RwLock<AHashMap<String, RwLock<String>>>> {